@@ -0,0 +1,103 @@
+//! Derive macro for [`jrand`](https://docs.rs/jrand)'s `FromJavaRng` trait.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::{
+    parse_macro_input, parse_quote, spanned::Spanned, Data, DeriveInput, Error, Fields,
+    GenericParam,
+};
+
+/// Derive `FromJavaRng` by generating every field in turn.
+///
+/// - Structs generate their fields in declaration order, so reordering
+///   fields changes the values a seed gives.
+/// - Enums draw `nextInt(n)` to pick one of their `n` variants in
+///   declaration order, even if there is only one, then generate that
+///   variant's fields in order.
+///
+/// Every type parameter must implement `FromJavaRng` too. Enums without
+/// variants are rejected, as no value of them can be generated.
+#[proc_macro_derive(FromJavaRng)]
+pub fn derive_from_java_rng(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand(mut input: DeriveInput) -> syn::Result<TokenStream2> {
+    let body = match &input.data {
+        Data::Struct(data) => construct(quote!(Self), &data.fields),
+        Data::Enum(data) => {
+            if data.variants.is_empty() {
+                return Err(Error::new(
+                    input.ident.span(),
+                    "cannot derive `FromJavaRng` for an enum without variants",
+                ));
+            }
+
+            let len = i32::try_from(data.variants.len()).map_err(|_| {
+                Error::new(input.ident.span(), "too many variants for `nextInt(n)`")
+            })?;
+
+            let arms = data.variants.iter().zip(0_i32..).map(|(variant, index)| {
+                let ident = &variant.ident;
+                let value = construct(quote!(Self::#ident), &variant.fields);
+
+                quote!(#index => #value,)
+            });
+
+            quote! {
+                match ::jrand::JavaRng::next_i32_bounded(rng, #len) {
+                    #(#arms)*
+                    _ => ::core::unreachable!(),
+                }
+            }
+        }
+        Data::Union(data) => {
+            return Err(Error::new(
+                data.union_token.span(),
+                "cannot derive `FromJavaRng` for a union",
+            ))
+        }
+    };
+
+    for param in &mut input.generics.params {
+        if let GenericParam::Type(param) = param {
+            param.bounds.push(parse_quote!(::jrand::FromJavaRng));
+        }
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::jrand::FromJavaRng for #ident #ty_generics #where_clause {
+            #[inline]
+            fn from_rng(rng: &mut ::jrand::JavaRng) -> Self {
+                #body
+            }
+        }
+    })
+}
+
+/// Build `path` from `fields`, generating each in declaration order.
+fn construct(path: TokenStream2, fields: &Fields) -> TokenStream2 {
+    let values = fields.iter().map(|field| {
+        let ty = &field.ty;
+        let value = quote_spanned!(ty.span()=> <#ty as ::jrand::FromJavaRng>::from_rng(rng));
+
+        match &field.ident {
+            Some(ident) => quote!(#ident: #value),
+            None => value,
+        }
+    });
+
+    match fields {
+        Fields::Named(_) => quote!(#path { #(#values),* }),
+        Fields::Unnamed(_) => quote!(#path ( #(#values),* )),
+        Fields::Unit => path,
+    }
+}
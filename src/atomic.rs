@@ -0,0 +1,189 @@
+cfg_if::cfg_if! {
+    // Targets without 64-bit atomics need them emulated. Ones without any
+    // compare-exchange, like `thumbv6m`, also need the `critical-section`
+    // feature for it.
+    if #[cfg(feature = "portable-atomic")] {
+        pub(crate) use portable_atomic::{AtomicI64, Ordering};
+    } else {
+        pub(crate) use core::sync::atomic::{AtomicI64, Ordering};
+    }
+}
+
+use crate::{
+    consts,
+    random::{initial_scramble, next_seed},
+    JavaRng,
+};
+
+/// Random number generator that replicates `java.util.Random` shared
+/// between threads.
+///
+/// Like Java's, the state is an atomic updated by a compare-exchange loop,
+/// so threads sharing one generator interleave their draws exactly as Java
+/// threads sharing one `Random` do: every draw of `next` takes one whole
+/// step of the sequence, and no step is taken twice.
+///
+/// Methods drawing more than once, such as [`AtomicJavaRng::next_i64`], are
+/// not atomic as a whole, just as in Java, so another thread may draw in
+/// between their steps. There is no `next_gaussian`, as Java's relies on
+/// holding the generator's lock.
+#[derive(Debug)]
+pub struct AtomicJavaRng {
+    seed: AtomicI64,
+}
+
+impl AtomicJavaRng {
+    /// Create a random number generator with the specified `seed`.
+    #[inline]
+    #[must_use]
+    pub const fn with_seed(seed: i64) -> AtomicJavaRng {
+        AtomicJavaRng {
+            seed: AtomicI64::new(initial_scramble(seed)),
+        }
+    }
+
+    /// Create a random number generator with the specified internal
+    /// `state`, skipping the scramble of the seed.
+    ///
+    /// Only the low 48 bits of `state` are used.
+    #[inline]
+    #[must_use]
+    pub const fn from_state(state: i64) -> AtomicJavaRng {
+        AtomicJavaRng {
+            seed: AtomicI64::new(state & consts::MASK),
+        }
+    }
+
+    /// Reset the random number generator to the specified `seed`, like
+    /// `setSeed` in Java.
+    #[inline]
+    pub fn set_seed(&self, seed: i64) {
+        self.seed.store(initial_scramble(seed), Ordering::Relaxed);
+    }
+
+    /// Get the internal 48-bit state of the random number generator.
+    #[inline]
+    #[must_use]
+    pub fn state(&self) -> i64 {
+        self.seed.load(Ordering::Relaxed)
+    }
+
+    /// Get a [`JavaRng`] continuing from the current state.
+    #[inline]
+    #[must_use]
+    pub fn load(&self) -> JavaRng {
+        JavaRng::from_state(self.state())
+    }
+
+    /// Consume the random number generator, returning a [`JavaRng`]
+    /// continuing from its state.
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> JavaRng {
+        JavaRng::from_state(self.seed.into_inner())
+    }
+
+    #[inline]
+    fn next(&self, bits: u8) -> i32 {
+        let mut prev = self.seed.load(Ordering::Relaxed);
+
+        let next = loop {
+            let next = next_seed(prev);
+
+            match self
+                .seed
+                .compare_exchange_weak(prev, next, Ordering::Relaxed, Ordering::Relaxed)
+            {
+                Ok(_) => break next,
+                Err(next_prev) => prev = next_prev,
+            }
+        };
+
+        (next as u64 >> (48 - bits)) as i32
+    }
+
+    #[inline]
+    pub fn next_bytes(&self, bytes: &mut [u8]) {
+        bytes.chunks_mut(4).for_each(|chunk| {
+            let bytes = self.next_i32().to_le_bytes();
+
+            chunk.copy_from_slice(&bytes[..chunk.len()])
+        });
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn next_i32(&self) -> i32 {
+        self.next(32)
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `bound` is not positive.
+    #[inline]
+    #[must_use]
+    pub fn next_i32_bounded(&self, bound: i32) -> i32 {
+        assert!(bound > 0, "bound must be positive");
+
+        let max = bound - 1;
+
+        if bound & max == 0 {
+            return ((self.next(31) as i64).wrapping_mul(bound as i64) >> 31) as i32;
+        }
+
+        loop {
+            let bits = self.next(31);
+            let rem = bits % bound;
+
+            if bits.wrapping_sub(rem).wrapping_add(max) >= 0 {
+                break rem;
+            }
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn next_i64(&self) -> i64 {
+        let upper = (self.next_i32() as i64) << 32;
+        let lower = self.next_i32() as i64;
+
+        upper.wrapping_add(lower)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn next_bool(&self) -> bool {
+        self.next(1) != 0
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn next_f32(&self) -> f32 {
+        (self.next(24) as f32) * consts::FLOAT_UNIT
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn next_f64(&self) -> f64 {
+        let upper = (self.next(26) as i64) << 27;
+        let lower = self.next(27) as i64;
+
+        (upper.wrapping_add(lower) as f64) * consts::DOUBLE_UNIT
+    }
+}
+
+/// Share a random number generator, keeping its state but not its cached
+/// gaussian.
+impl From<JavaRng> for AtomicJavaRng {
+    #[inline]
+    fn from(rng: JavaRng) -> AtomicJavaRng {
+        AtomicJavaRng::from_state(rng.state())
+    }
+}
+
+impl Default for AtomicJavaRng {
+    #[inline]
+    fn default() -> AtomicJavaRng {
+        AtomicJavaRng::from(JavaRng::new())
+    }
+}
@@ -0,0 +1,136 @@
+use crate::{consts, JavaRng};
+
+/// Random number generator that replicates `java.util.Random`, computing
+/// its states `N` at a time.
+///
+/// Each state is computed straight from the last state of the previous
+/// block with a precomputed jump, rather than from the state before it, so
+/// a block has no dependency chain and can be unrolled or vectorized. The
+/// values generated are exactly those of a [`JavaRng`] with the same state.
+#[derive(Debug, Clone)]
+pub struct BufferedRng<const N: usize> {
+    /// The state the current block was computed from.
+    seed: i64,
+    states: [i64; N],
+    /// The index of the next unused state, `N` when the block is used up.
+    index: usize,
+    next_gaussian: Option<f64>,
+}
+
+impl<const N: usize> BufferedRng<N> {
+    /// The multiplier and addend advancing a state by each of `1..=N` steps.
+    const JUMPS: [(i64, i64); N] = {
+        let mut jumps = [(0, 0); N];
+        let (mut mul, mut add) = (consts::MULTIPLIER, consts::ADDEND);
+        let mut i = 0;
+
+        while i < N {
+            jumps[i] = (mul, add);
+
+            mul = mul.wrapping_mul(consts::MULTIPLIER);
+            add = add
+                .wrapping_mul(consts::MULTIPLIER)
+                .wrapping_add(consts::ADDEND);
+            i += 1;
+        }
+
+        jumps
+    };
+
+    /// Create a random number generator continuing from `rng`, keeping its
+    /// cached gaussian.
+    #[inline]
+    #[must_use]
+    pub const fn new(rng: JavaRng) -> BufferedRng<N> {
+        assert!(N > 0, "the buffer must not be empty");
+
+        // A used up block ending at the state of `rng`.
+        let mut states = [0; N];
+        states[N - 1] = rng.seed;
+
+        BufferedRng {
+            seed: rng.seed,
+            states,
+            index: N,
+            next_gaussian: rng.next_gaussian,
+        }
+    }
+
+    /// Create a random number generator with the specified `seed`.
+    #[inline]
+    #[must_use]
+    pub const fn with_seed(seed: i64) -> BufferedRng<N> {
+        BufferedRng::new(JavaRng::with_seed(seed))
+    }
+
+    /// Get the internal 48-bit state of the random number generator.
+    #[inline]
+    #[must_use]
+    pub const fn state(&self) -> i64 {
+        if self.index == 0 {
+            self.seed
+        } else {
+            self.states[self.index - 1]
+        }
+    }
+
+    /// Consume the random number generator, returning a [`JavaRng`]
+    /// continuing from its state.
+    #[inline]
+    #[must_use]
+    pub const fn into_inner(self) -> JavaRng {
+        JavaRng {
+            seed: self.state(),
+            next_gaussian: self.next_gaussian,
+        }
+    }
+
+    #[inline]
+    fn refill(&mut self) {
+        self.seed = self.state();
+
+        for (state, &(mul, add)) in self.states.iter_mut().zip(&Self::JUMPS) {
+            *state = self.seed.wrapping_mul(mul).wrapping_add(add) & consts::MASK;
+        }
+
+        self.index = 0;
+    }
+
+    #[inline]
+    #[must_use]
+    fn next(&mut self, bits: u8) -> i32 {
+        if self.index == N {
+            self.refill();
+        }
+
+        let state = self.states[self.index];
+        self.index += 1;
+
+        (state as u64 >> (48 - bits)) as i32
+    }
+
+    #[inline]
+    pub fn next_bytes(&mut self, bytes: &mut [u8]) {
+        bytes.chunks_mut(4).for_each(|chunk| {
+            let bytes = self.next_i32().to_le_bytes();
+
+            chunk.copy_from_slice(&bytes[..chunk.len()])
+        });
+    }
+}
+
+impl_random!([const N: usize] BufferedRng<N>);
+
+impl<const N: usize> From<JavaRng> for BufferedRng<N> {
+    #[inline]
+    fn from(rng: JavaRng) -> BufferedRng<N> {
+        BufferedRng::new(rng)
+    }
+}
+
+impl<const N: usize> From<BufferedRng<N>> for JavaRng {
+    #[inline]
+    fn from(rng: BufferedRng<N>) -> JavaRng {
+        rng.into_inner()
+    }
+}
@@ -0,0 +1,161 @@
+//! Random number generators replicating the `org.apache.commons.math3.random`
+//! package of Apache Commons Math 3.6.
+//!
+//! Every generator here derives its values from `next(bits)` the way
+//! Commons Math's `BitsStreamGenerator` does, which is close to, but not the
+//! same as, `java.util.Random`.
+
+/// Implement the methods Commons Math's `BitsStreamGenerator` derives from
+/// `next(bits)`.
+///
+/// The type must provide `fn next(&mut self, bits: u8) -> i32`, and have a
+/// `next_gaussian: Option<f64>` field caching the second gaussian.
+macro_rules! impl_bits_stream {
+    ($ty:ty) => {
+        const _: () = {
+            impl $ty {
+                /// Fill `bytes` with the little-endian bytes of successive
+                /// `next_i32` calls.
+                #[inline]
+                pub fn next_bytes(&mut self, bytes: &mut [u8]) {
+                    bytes.chunks_mut(4).for_each(|chunk| {
+                        let bytes = self.next_i32().to_le_bytes();
+
+                        chunk.copy_from_slice(&bytes[..chunk.len()])
+                    });
+                }
+
+                #[inline]
+                pub fn next_bytes_signed(&mut self, bytes: &mut [i8]) {
+                    self.next_bytes(bytemuck::cast_slice_mut(bytes))
+                }
+
+                #[inline]
+                #[must_use]
+                pub fn next_i32(&mut self) -> i32 {
+                    self.next(32)
+                }
+
+                #[inline]
+                #[must_use]
+                pub fn next_i32_bounded(&mut self, bound: i32) -> i32 {
+                    assert!(bound > 0, "bound must be positive");
+
+                    let max = bound - 1;
+
+                    if bound & max == 0 {
+                        return ((self.next(31) as i64).wrapping_mul(bound as i64) >> 31) as i32;
+                    }
+
+                    loop {
+                        let bits = self.next(31);
+                        let rem = bits % bound;
+
+                        if bits.wrapping_sub(rem).wrapping_add(max) >= 0 {
+                            break rem;
+                        }
+                    }
+                }
+
+                #[inline]
+                #[must_use]
+                pub fn next_i64(&mut self) -> i64 {
+                    let high = (self.next(32) as i64) << 32;
+                    let low = self.next(32) as u32 as i64;
+
+                    high | low
+                }
+
+                /// Generate an `i64` in `[0, bound)` from 63 random bits,
+                /// rejecting the final partial multiple of `bound`.
+                #[inline]
+                #[must_use]
+                pub fn next_i64_bounded(&mut self, bound: i64) -> i64 {
+                    assert!(bound > 0, "bound must be positive");
+
+                    loop {
+                        let bits = ((self.next(31) as i64) << 32) | self.next(32) as u32 as i64;
+                        let rem = bits % bound;
+
+                        if bits.wrapping_sub(rem).wrapping_add(bound - 1) >= 0 {
+                            break rem;
+                        }
+                    }
+                }
+
+                #[inline]
+                #[must_use]
+                pub fn next_bool(&mut self) -> bool {
+                    self.next(1) != 0
+                }
+
+                /// Generate an `f32` in `[0, 1)` from 23 random bits.
+                #[inline]
+                #[must_use]
+                pub fn next_f32(&mut self) -> f32 {
+                    self.next(23) as f32 * (1.0 / (1 << 23) as f32)
+                }
+
+                /// Generate an `f64` in `[0, 1)` from 52 random bits.
+                #[inline]
+                #[must_use]
+                pub fn next_f64(&mut self) -> f64 {
+                    let high = (self.next(26) as i64) << 26;
+                    let low = self.next(26) as i64;
+
+                    (high | low) as f64 * (1.0 / (1_i64 << 52) as f64)
+                }
+
+                /// Generate a gaussian with the basic Box–Muller transform,
+                /// caching the sine half of each pair.
+                ///
+                /// This uses [`Fast`]($crate::Fast) math. Commons Math evaluates
+                /// the transform with its own `FastMath`, which can differ from
+                /// either policy in the last bit.
+                #[inline]
+                #[must_use]
+                pub fn next_gaussian(&mut self) -> f64 {
+                    self.next_gaussian_using::<$crate::Fast>()
+                }
+
+                /// Generate a gaussian like [`Self::next_gaussian`], computed
+                /// with the math of `P`.
+                ///
+                /// A cached gaussian is returned as is, whichever policy it was
+                /// computed with.
+                #[inline]
+                #[must_use]
+                pub fn next_gaussian_using<P: $crate::MathPolicy>(&mut self) -> f64 {
+                    if let Some(next_gaussian) = self.next_gaussian.take() {
+                        return next_gaussian;
+                    }
+
+                    let x = self.next_f64();
+                    let y = self.next_f64();
+
+                    let alpha = 2.0 * ::core::f64::consts::PI * x;
+                    let r = P::sqrt(-2.0 * P::ln(y));
+
+                    self.next_gaussian = Some(r * P::sin(alpha));
+
+                    r * P::cos(alpha)
+                }
+
+                /// Discard the cached gaussian, like Commons Math's `clear()`.
+                #[inline]
+                pub fn clear_gaussian(&mut self) {
+                    self.next_gaussian = None;
+                }
+            }
+        };
+    };
+}
+
+mod isaac;
+pub use isaac::*;
+
+mod mersenne_twister;
+pub use mersenne_twister::*;
+
+mod well;
+pub use well::*;
@@ -0,0 +1,208 @@
+use core::fmt;
+
+const SIZE_L: u32 = 8;
+const SIZE: usize = 1 << SIZE_L;
+const H_SIZE: usize = SIZE >> 1;
+const GOLDEN_RATIO: u32 = 0x9e37_79b9;
+
+/// Random number generator that replicates the behavior of
+/// `org.apache.commons.math3.random.ISAACRandom` in Commons Math.
+#[derive(Clone)]
+pub struct IsaacRng {
+    rsl: [u32; SIZE],
+    mem: [u32; SIZE],
+    count: usize,
+    a: u32,
+    b: u32,
+    c: u32,
+    pub(crate) next_gaussian: Option<f64>,
+}
+
+impl IsaacRng {
+    /// Create a random number generator with the specified `long` seed.
+    #[inline]
+    #[must_use]
+    pub fn with_seed(seed: i64) -> IsaacRng {
+        let mut rng = IsaacRng::zeroed();
+        rng.set_seed(seed);
+        rng
+    }
+
+    /// Create a random number generator with the specified `int` seed.
+    #[inline]
+    #[must_use]
+    pub fn with_seed_i32(seed: i32) -> IsaacRng {
+        let mut rng = IsaacRng::zeroed();
+        rng.set_seed_i32(seed);
+        rng
+    }
+
+    /// Create a random number generator with the specified `int[]` seed.
+    #[inline]
+    #[must_use]
+    pub fn with_seed_slice(seed: &[i32]) -> IsaacRng {
+        let mut rng = IsaacRng::zeroed();
+        rng.set_seed_slice(seed);
+        rng
+    }
+
+    #[inline]
+    const fn zeroed() -> IsaacRng {
+        IsaacRng {
+            rsl: [0; SIZE],
+            mem: [0; SIZE],
+            count: 0,
+            a: 0,
+            b: 0,
+            c: 0,
+            next_gaussian: None,
+        }
+    }
+
+    /// Reseed with a `long`, which Commons Math splits into the `int[]` seed
+    /// `{ high, low }`.
+    #[inline]
+    pub fn set_seed(&mut self, seed: i64) {
+        self.set_seed_slice(&[(seed >> 32) as i32, seed as i32]);
+    }
+
+    /// Reseed with an `int`, which Commons Math treats as the `int[]` seed
+    /// `{ seed }`.
+    #[inline]
+    pub fn set_seed_i32(&mut self, seed: i32) {
+        self.set_seed_slice(&[seed]);
+    }
+
+    /// Reseed with an `int[]`.
+    ///
+    /// Seeds shorter than 256 words are extended like `init_genrand`, with the
+    /// previous word sign-extended as in Commons Math. An empty seed extends
+    /// the last block of output instead.
+    pub fn set_seed_slice(&mut self, seed: &[i32]) {
+        let len = seed.len().min(SIZE);
+
+        self.rsl
+            .iter_mut()
+            .zip(&seed[..len])
+            .for_each(|(r, &s)| *r = s as u32);
+
+        for j in seed.len()..SIZE {
+            let k = self.rsl[j - seed.len()] as i32 as i64;
+
+            self.rsl[j] = 0x6c07_8965_i64
+                .wrapping_mul(k ^ (k >> 30))
+                .wrapping_add(j as i64) as u32;
+        }
+
+        self.init_state();
+    }
+
+    fn init_state(&mut self) {
+        self.a = 0;
+        self.b = 0;
+        self.c = 0;
+
+        let mut arr = [GOLDEN_RATIO; 8];
+
+        for _ in 0..4 {
+            shuffle(&mut arr);
+        }
+
+        // Fill in `mem` with messy stuff, then make a second pass so all of
+        // the seed affects all of `mem`.
+        for pass in 0..2 {
+            for j in (0..SIZE).step_by(8) {
+                let src = if pass == 0 { &self.rsl } else { &self.mem };
+
+                arr.iter_mut()
+                    .zip(&src[j..j + 8])
+                    .for_each(|(a, &s)| *a = a.wrapping_add(s));
+
+                shuffle(&mut arr);
+
+                self.mem[j..j + 8].copy_from_slice(&arr);
+            }
+        }
+
+        self.isaac();
+        self.count = SIZE;
+        self.next_gaussian = None;
+    }
+}
+
+impl IsaacRng {
+    #[inline]
+    #[must_use]
+    pub(crate) fn next(&mut self, bits: u8) -> i32 {
+        if self.count == 0 {
+            self.isaac();
+            self.count = SIZE;
+        }
+
+        self.count -= 1;
+
+        (self.rsl[self.count] >> (32 - bits)) as i32
+    }
+
+    /// Generate the next block of `SIZE` results.
+    fn isaac(&mut self) {
+        self.c = self.c.wrapping_add(1);
+        self.b = self.b.wrapping_add(self.c);
+
+        for i in 0..SIZE {
+            let x = self.mem[i];
+
+            self.a ^= match i % 4 {
+                0 => self.a << 13,
+                1 => self.a >> 6,
+                2 => self.a << 2,
+                _ => self.a >> 16,
+            };
+            self.a = self.a.wrapping_add(self.mem[(i + H_SIZE) % SIZE]);
+
+            let y = self.mem[ind(x)].wrapping_add(self.a).wrapping_add(self.b);
+
+            self.mem[i] = y;
+            self.b = self.mem[ind(y >> SIZE_L)].wrapping_add(x);
+            self.rsl[i] = self.b;
+        }
+    }
+}
+
+impl_bits_stream!(IsaacRng);
+
+impl fmt::Debug for IsaacRng {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IsaacRng")
+            .field("count", &self.count)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Index `mem` with bits 2 through 9 of `x`.
+#[inline]
+const fn ind(x: u32) -> usize {
+    (x as usize >> 2) & (SIZE - 1)
+}
+
+#[inline]
+fn shuffle(arr: &mut [u32; 8]) {
+    const SHIFTS: [(bool, u32); 8] = [
+        (true, 11),
+        (false, 2),
+        (true, 8),
+        (false, 16),
+        (true, 10),
+        (false, 4),
+        (true, 8),
+        (false, 9),
+    ];
+
+    for (i, (left, shift)) in SHIFTS.into_iter().enumerate() {
+        let next = arr[(i + 1) % 8];
+
+        arr[i] ^= if left { next << shift } else { next >> shift };
+        arr[(i + 3) % 8] = arr[(i + 3) % 8].wrapping_add(arr[i]);
+        arr[(i + 1) % 8] = next.wrapping_add(arr[(i + 2) % 8]);
+    }
+}
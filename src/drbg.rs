@@ -0,0 +1,535 @@
+//! NIST SP 800-90A deterministic random bit generators that replicate the
+//! `DRBG` implementation of `java.security.SecureRandom` shipped with the JDK.
+//!
+//! Java instantiates a `DRBG` as soon as it is first used. If `setSeed` is
+//! called before that, the seed becomes the entropy input of the
+//! instantiation, which makes the whole output stream reproducible. This is
+//! what [`Drbg::with_seed`] replicates.
+
+use core::fmt;
+
+mod ctr;
+mod hash;
+mod hmac;
+
+/// The largest `seedlen` used by any mechanism, in bytes.
+const MAX_SEED_LEN: usize = 111;
+
+/// The largest digest output used by any mechanism, in bytes.
+const MAX_OUT_LEN: usize = 64;
+
+/// The DRBG mechanism, as named in the `securerandom.drbg.config`
+/// security property.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Mechanism {
+    /// `Hash_DRBG`, the JDK default.
+    #[default]
+    Hash,
+    /// `HMAC_DRBG`.
+    Hmac,
+    /// `CTR_DRBG`.
+    Ctr,
+}
+
+impl Mechanism {
+    /// The name Java uses for this mechanism.
+    #[inline]
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Mechanism::Hash => "Hash_DRBG",
+            Mechanism::Hmac => "HMAC_DRBG",
+            Mechanism::Ctr => "CTR_DRBG",
+        }
+    }
+}
+
+/// The underlying hash function or block cipher of a DRBG.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Algorithm {
+    Sha224,
+    Sha512_224,
+    Sha256,
+    Sha512_256,
+    Sha384,
+    Sha512,
+    Aes128,
+    Aes192,
+    Aes256,
+}
+
+impl Algorithm {
+    /// The name Java uses for this algorithm.
+    #[inline]
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Algorithm::Sha224 => "SHA-224",
+            Algorithm::Sha512_224 => "SHA-512/224",
+            Algorithm::Sha256 => "SHA-256",
+            Algorithm::Sha512_256 => "SHA-512/256",
+            Algorithm::Sha384 => "SHA-384",
+            Algorithm::Sha512 => "SHA-512",
+            Algorithm::Aes128 => "AES-128",
+            Algorithm::Aes192 => "AES-192",
+            Algorithm::Aes256 => "AES-256",
+        }
+    }
+
+    /// Look up an algorithm by its Java name, ignoring ASCII case.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Algorithm> {
+        const ALL: [Algorithm; 9] = [
+            Algorithm::Sha224,
+            Algorithm::Sha512_224,
+            Algorithm::Sha256,
+            Algorithm::Sha512_256,
+            Algorithm::Sha384,
+            Algorithm::Sha512,
+            Algorithm::Aes128,
+            Algorithm::Aes192,
+            Algorithm::Aes256,
+        ];
+
+        ALL.into_iter()
+            .find(|alg| alg.name().eq_ignore_ascii_case(name))
+    }
+
+    /// The highest security strength this algorithm supports, in bits.
+    #[inline]
+    #[must_use]
+    pub const fn max_strength(self) -> u32 {
+        match self {
+            Algorithm::Sha224 | Algorithm::Sha512_224 | Algorithm::Aes192 => 192,
+            Algorithm::Aes128 => 128,
+            _ => 256,
+        }
+    }
+
+    #[inline]
+    const fn is_cipher(self) -> bool {
+        matches!(
+            self,
+            Algorithm::Aes128 | Algorithm::Aes192 | Algorithm::Aes256
+        )
+    }
+}
+
+/// Configuration of a [`Drbg`].
+///
+/// This combines the `securerandom.drbg.config` security property
+/// (mechanism, algorithm and derivation function) with the requested
+/// strength of `DrbgParameters.instantiation`. The default matches
+/// `SecureRandom.getInstance("DRBG")` on a stock JDK, that is
+/// `Hash_DRBG,SHA-256,128,reseed_only`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DrbgConfig {
+    pub mechanism: Mechanism,
+    /// The algorithm, or `None` to let Java pick one from the strength.
+    pub algorithm: Option<Algorithm>,
+    /// The requested security strength in bits, or `None` for the default.
+    pub strength: Option<u32>,
+    /// Whether `CTR_DRBG` uses a derivation function. Ignored by the other
+    /// mechanisms.
+    pub use_df: bool,
+}
+
+impl DrbgConfig {
+    /// The configuration Java uses when none is specified.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> DrbgConfig {
+        DrbgConfig {
+            mechanism: Mechanism::Hash,
+            algorithm: None,
+            strength: None,
+            use_df: true,
+        }
+    }
+}
+
+impl Default for DrbgConfig {
+    fn default() -> Self {
+        DrbgConfig::new()
+    }
+}
+
+/// Errors reported when a [`Drbg`] is misconfigured or misused, mirroring
+/// the `IllegalArgumentException`s Java throws.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DrbgError {
+    /// The algorithm cannot be used with the mechanism.
+    UnsupportedAlgorithm(Mechanism, Algorithm),
+    /// The requested strength is not supported.
+    UnsupportedStrength(u32),
+    /// An input does not have a length accepted by the mechanism.
+    InvalidLength,
+}
+
+impl fmt::Display for DrbgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DrbgError::UnsupportedAlgorithm(mech, alg) => {
+                write!(f, "{} not supported in {}", alg.name(), mech.name())
+            }
+            DrbgError::UnsupportedStrength(strength) => {
+                write!(f, "unsupported strength {strength}")
+            }
+            DrbgError::InvalidLength => f.write_str("invalid input length"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DrbgError {}
+
+// The AES key schedules dominate the size, and there is no allocator to box them.
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone)]
+enum State {
+    Hash(hash::HashDrbg),
+    Hmac(hmac::HmacDrbg),
+    Ctr(ctr::CtrDrbg),
+}
+
+/// Deterministic random bit generator that replicates the behavior of
+/// Java's `DRBG` `SecureRandom`.
+#[derive(Clone)]
+pub struct Drbg {
+    state: State,
+    mechanism: Mechanism,
+    algorithm: Algorithm,
+    strength: u32,
+    use_df: bool,
+}
+
+impl Drbg {
+    /// Create a generator the same way Java does when `setSeed(seed)` is the
+    /// first call made on `SecureRandom.getInstance("DRBG")`.
+    ///
+    /// The seed is zero-padded or truncated to the lengths Java accepts, and
+    /// the nonce is the one handed to the first DRBG instantiated by a JVM,
+    /// see [`Drbg::jvm_nonce`].
+    pub fn with_seed(config: DrbgConfig, seed: &[u8]) -> Result<Drbg, DrbgError> {
+        let params = Params::resolve(config)?;
+        let mut entropy = [0; MAX_SEED_LEN];
+        let entropy = params.normalize(seed, &mut entropy);
+
+        Drbg::instantiate_with(params, entropy, &Drbg::jvm_nonce(1), None)
+    }
+
+    /// Instantiate a generator from explicit entropy input, nonce and
+    /// personalization string, as described by SP 800-90A.
+    ///
+    /// Unlike [`Drbg::with_seed`], the inputs are used as is.
+    pub fn instantiate(
+        config: DrbgConfig,
+        entropy: &[u8],
+        nonce: &[u8],
+        personalization: Option<&[u8]>,
+    ) -> Result<Drbg, DrbgError> {
+        let params = Params::resolve(config)?;
+
+        if entropy.len() < params.min_length || entropy.len() > params.max_length {
+            return Err(DrbgError::InvalidLength);
+        }
+
+        Drbg::instantiate_with(params, entropy, nonce, personalization)
+    }
+
+    fn instantiate_with(
+        params: Params,
+        entropy: &[u8],
+        nonce: &[u8],
+        personalization: Option<&[u8]>,
+    ) -> Result<Drbg, DrbgError> {
+        let state = match params.mechanism {
+            Mechanism::Hash => State::Hash(hash::HashDrbg::instantiate(
+                params.algorithm,
+                entropy,
+                nonce,
+                personalization,
+            )),
+            Mechanism::Hmac => State::Hmac(hmac::HmacDrbg::instantiate(
+                params.algorithm,
+                entropy,
+                nonce,
+                personalization,
+            )),
+            Mechanism::Ctr => State::Ctr(ctr::CtrDrbg::instantiate(
+                params.algorithm,
+                params.use_df,
+                entropy,
+                nonce,
+                personalization,
+            )?),
+        };
+
+        Ok(Drbg {
+            state,
+            mechanism: params.mechanism,
+            algorithm: params.algorithm,
+            strength: params.strength,
+            use_df: params.use_df,
+        })
+    }
+
+    /// Check that `config` resolves to a supported generator.
+    #[inline]
+    pub(crate) fn validate(config: DrbgConfig) -> Result<(), DrbgError> {
+        Params::resolve(config).map(|_| ())
+    }
+
+    /// The nonce Java uses for the `count`-th DRBG instantiated in a JVM,
+    /// counting from one.
+    #[inline]
+    #[must_use]
+    pub const fn jvm_nonce(count: u128) -> [u8; 16] {
+        count.to_be_bytes()
+    }
+}
+
+impl Drbg {
+    /// The mechanism of this generator.
+    #[inline]
+    #[must_use]
+    pub fn mechanism(&self) -> Mechanism {
+        self.mechanism
+    }
+
+    /// The algorithm of this generator.
+    #[inline]
+    #[must_use]
+    pub fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    /// The security strength of this generator, in bits.
+    #[inline]
+    #[must_use]
+    pub fn strength(&self) -> u32 {
+        self.strength
+    }
+
+    /// Whether this generator uses a derivation function.
+    #[inline]
+    #[must_use]
+    pub fn use_df(&self) -> bool {
+        self.use_df
+    }
+
+    /// Fill `bytes` with one generate request, like `nextBytes`.
+    #[inline]
+    pub fn next_bytes(&mut self, bytes: &mut [u8]) {
+        self.generate(bytes, None)
+            .expect("generating without additional input cannot fail")
+    }
+
+    /// Fill `bytes` with one generate request using `additional_input`, like
+    /// `nextBytes(bytes, DrbgParameters.nextBytes(strength, false, input))`.
+    #[inline]
+    pub fn next_bytes_with(
+        &mut self,
+        bytes: &mut [u8],
+        additional_input: &[u8],
+    ) -> Result<(), DrbgError> {
+        self.generate(bytes, Some(additional_input))
+    }
+
+    /// Reseed the generator with explicit entropy input, like
+    /// `reseed(DrbgParameters.reseed(false, additional_input))` does with the
+    /// entropy obtained from its entropy source.
+    pub fn reseed(
+        &mut self,
+        entropy: &[u8],
+        additional_input: Option<&[u8]>,
+    ) -> Result<(), DrbgError> {
+        let params = self.params();
+
+        if entropy.len() < params.min_length || entropy.len() > params.max_length {
+            return Err(DrbgError::InvalidLength);
+        }
+
+        if additional_input.is_some_and(|input| input.len() > params.max_input_length) {
+            return Err(DrbgError::InvalidLength);
+        }
+
+        match &mut self.state {
+            State::Hash(drbg) => drbg.reseed(entropy, additional_input),
+            State::Hmac(drbg) => drbg.reseed(entropy, additional_input),
+            State::Ctr(drbg) => drbg.reseed(entropy, additional_input)?,
+        }
+
+        Ok(())
+    }
+
+    /// Reseed the generator like calling `setSeed(seed)` on an already
+    /// instantiated `DRBG`.
+    pub fn set_seed(&mut self, seed: &[u8]) {
+        let params = self.params();
+        let mut entropy = [0; MAX_SEED_LEN];
+        let entropy = params.normalize(seed, &mut entropy);
+
+        self.reseed(entropy, None)
+            .expect("normalized seeds are always accepted")
+    }
+
+    fn generate(
+        &mut self,
+        bytes: &mut [u8],
+        additional_input: Option<&[u8]>,
+    ) -> Result<(), DrbgError> {
+        if additional_input.is_some_and(|input| input.len() > self.params().max_input_length) {
+            return Err(DrbgError::InvalidLength);
+        }
+
+        match &mut self.state {
+            State::Hash(drbg) => drbg.generate(bytes, additional_input),
+            State::Hmac(drbg) => drbg.generate(bytes, additional_input),
+            State::Ctr(drbg) => drbg.generate(bytes, additional_input),
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn params(&self) -> Params {
+        Params::new(self.mechanism, self.algorithm, self.strength, self.use_df)
+    }
+}
+
+impl fmt::Debug for Drbg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The internal state is deliberately left out.
+        f.debug_struct("Drbg")
+            .field("mechanism", &self.mechanism)
+            .field("algorithm", &self.algorithm)
+            .field("strength", &self.strength)
+            .field("use_df", &self.use_df)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Resolved configuration, following `chooseAlgorithmAndStrength`.
+#[derive(Debug, Clone, Copy)]
+struct Params {
+    mechanism: Mechanism,
+    algorithm: Algorithm,
+    strength: u32,
+    use_df: bool,
+    min_length: usize,
+    max_length: usize,
+    max_input_length: usize,
+}
+
+impl Params {
+    fn new(mechanism: Mechanism, algorithm: Algorithm, strength: u32, use_df: bool) -> Params {
+        let (min_length, max_length, max_input_length) = match mechanism {
+            Mechanism::Ctr if !use_df => {
+                let seed_len = ctr::seed_len(algorithm);
+
+                (seed_len, seed_len, seed_len)
+            }
+            _ => (
+                (strength / 8) as usize,
+                i32::MAX as usize,
+                i32::MAX as usize,
+            ),
+        };
+
+        Params {
+            mechanism,
+            algorithm,
+            strength,
+            use_df: mechanism != Mechanism::Ctr || use_df,
+            min_length,
+            max_length,
+            max_input_length,
+        }
+    }
+
+    fn resolve(config: DrbgConfig) -> Result<Params, DrbgError> {
+        let DrbgConfig {
+            mechanism,
+            algorithm,
+            strength,
+            use_df,
+        } = config;
+
+        if let Some(strength) = strength {
+            if strength > 256 {
+                return Err(DrbgError::UnsupportedStrength(strength));
+            }
+        }
+
+        let (algorithm, strength) = match algorithm {
+            Some(algorithm) => {
+                if algorithm.is_cipher() != (mechanism == Mechanism::Ctr) {
+                    return Err(DrbgError::UnsupportedAlgorithm(mechanism, algorithm));
+                }
+
+                let max = algorithm.max_strength();
+
+                let strength = match strength {
+                    Some(strength) => match standard_strength(strength) {
+                        standard if standard > max => {
+                            return Err(DrbgError::UnsupportedStrength(strength))
+                        }
+                        standard => standard,
+                    },
+                    None => max.min(128),
+                };
+
+                (algorithm, strength)
+            }
+            None => {
+                let strength = standard_strength(strength.unwrap_or(128));
+
+                let algorithm = match mechanism {
+                    // Modern JDKs have no AES key length limit, so Java always
+                    // picks AES-256 here.
+                    Mechanism::Ctr => Algorithm::Aes256,
+                    _ => Algorithm::Sha256,
+                };
+
+                (algorithm, strength)
+            }
+        };
+
+        Ok(Params::new(mechanism, algorithm, strength, use_df))
+    }
+
+    /// Pad or truncate a seed the way `engineSetSeed` does.
+    fn normalize<'a>(&self, seed: &'a [u8], buf: &'a mut [u8; MAX_SEED_LEN]) -> &'a [u8] {
+        if seed.len() < self.min_length {
+            buf[..seed.len()].copy_from_slice(seed);
+
+            &buf[..self.min_length]
+        } else {
+            &seed[..seed.len().min(self.max_length)]
+        }
+    }
+}
+
+#[inline]
+const fn standard_strength(strength: u32) -> u32 {
+    match strength {
+        0..=112 => 112,
+        113..=128 => 128,
+        129..=192 => 192,
+        _ => 256,
+    }
+}
+
+/// Add the big-endian number `x` to the big-endian number `v`, modulo
+/// `2^(8 * v.len())`.
+fn add_be(v: &mut [u8], x: &[u8]) {
+    let mut carry = 0u16;
+
+    for (i, byte) in v.iter_mut().rev().enumerate() {
+        let sum = *byte as u16 + carry + x.len().checked_sub(i + 1).map_or(0, |j| x[j] as u16);
+
+        *byte = sum as u8;
+        carry = sum >> 8;
+    }
+}
@@ -0,0 +1,243 @@
+use super::{Algorithm, DrbgError, MAX_SEED_LEN};
+use aes::{
+    cipher::{generic_array::GenericArray, BlockEncrypt, KeyInit},
+    Aes128, Aes192, Aes256,
+};
+
+const BLOCK_LEN: usize = 16;
+
+/// An AES key schedule selected at runtime.
+#[derive(Clone)]
+enum Cipher {
+    Aes128(Aes128),
+    Aes192(Aes192),
+    Aes256(Aes256),
+}
+
+impl Cipher {
+    fn new(algorithm: Algorithm, key: &[u8]) -> Cipher {
+        const INVALID_KEY: &str = "key length must match the cipher";
+
+        match algorithm {
+            Algorithm::Aes128 => Cipher::Aes128(Aes128::new_from_slice(key).expect(INVALID_KEY)),
+            Algorithm::Aes192 => Cipher::Aes192(Aes192::new_from_slice(key).expect(INVALID_KEY)),
+            Algorithm::Aes256 => Cipher::Aes256(Aes256::new_from_slice(key).expect(INVALID_KEY)),
+            _ => unreachable!("{} is not a block cipher", algorithm.name()),
+        }
+    }
+
+    #[inline]
+    fn encrypt(&self, block: &mut [u8; BLOCK_LEN]) {
+        let block = GenericArray::from_mut_slice(block);
+
+        match self {
+            Cipher::Aes128(cipher) => cipher.encrypt_block(block),
+            Cipher::Aes192(cipher) => cipher.encrypt_block(block),
+            Cipher::Aes256(cipher) => cipher.encrypt_block(block),
+        }
+    }
+}
+
+/// The key length of a block cipher, in bytes.
+#[inline]
+const fn key_len(algorithm: Algorithm) -> usize {
+    match algorithm {
+        Algorithm::Aes128 => 16,
+        Algorithm::Aes192 => 24,
+        _ => 32,
+    }
+}
+
+/// The `seedlen` of `CTR_DRBG`, in bytes.
+#[inline]
+pub(super) const fn seed_len(algorithm: Algorithm) -> usize {
+    key_len(algorithm) + BLOCK_LEN
+}
+
+#[derive(Clone)]
+pub(super) struct CtrDrbg {
+    algorithm: Algorithm,
+    use_df: bool,
+    cipher: Cipher,
+    v: [u8; BLOCK_LEN],
+}
+
+impl CtrDrbg {
+    pub(super) fn instantiate(
+        algorithm: Algorithm,
+        use_df: bool,
+        entropy: &[u8],
+        nonce: &[u8],
+        personalization: Option<&[u8]>,
+    ) -> Result<CtrDrbg, DrbgError> {
+        let mut drbg = CtrDrbg {
+            algorithm,
+            use_df,
+            cipher: Cipher::new(algorithm, &[0; 32][..key_len(algorithm)]),
+            v: [0; BLOCK_LEN],
+        };
+
+        let personalization = personalization.unwrap_or_default();
+
+        // Java passes `nonce || personalization` as the additional input of
+        // a reseed. Without a derivation function the nonce is not used.
+        if use_df {
+            let mut seed = [0; MAX_SEED_LEN];
+            drbg.df(&[entropy, nonce, personalization], &mut seed);
+            drbg.update(&seed);
+        } else {
+            drbg.update(&drbg.xor_input(entropy, personalization)?);
+        }
+
+        Ok(drbg)
+    }
+
+    pub(super) fn reseed(
+        &mut self,
+        entropy: &[u8],
+        additional_input: Option<&[u8]>,
+    ) -> Result<(), DrbgError> {
+        let additional_input = additional_input.unwrap_or_default();
+
+        if self.use_df {
+            let mut seed = [0; MAX_SEED_LEN];
+            self.df(&[entropy, additional_input], &mut seed);
+            self.update(&seed);
+        } else {
+            self.update(&self.xor_input(entropy, additional_input)?);
+        }
+
+        Ok(())
+    }
+
+    pub(super) fn generate(&mut self, bytes: &mut [u8], additional_input: Option<&[u8]>) {
+        let mut input = [0; MAX_SEED_LEN];
+
+        if let Some(additional_input) = additional_input {
+            if self.use_df {
+                self.df(&[additional_input], &mut input);
+            } else {
+                input[..additional_input.len()].copy_from_slice(additional_input);
+            }
+
+            self.update(&input);
+        }
+
+        for chunk in bytes.chunks_mut(BLOCK_LEN) {
+            increment(&mut self.v);
+
+            let mut block = self.v;
+            self.cipher.encrypt(&mut block);
+            chunk.copy_from_slice(&block[..chunk.len()]);
+        }
+
+        self.update(&input);
+    }
+
+    #[inline]
+    fn seed_len(&self) -> usize {
+        seed_len(self.algorithm)
+    }
+
+    /// Combine entropy and an additional input without a derivation function.
+    fn xor_input(&self, entropy: &[u8], input: &[u8]) -> Result<[u8; MAX_SEED_LEN], DrbgError> {
+        if entropy.len() != self.seed_len() || input.len() > self.seed_len() {
+            return Err(DrbgError::InvalidLength);
+        }
+
+        let mut seed = [0; MAX_SEED_LEN];
+        seed[..entropy.len()].copy_from_slice(entropy);
+        seed.iter_mut().zip(input).for_each(|(s, i)| *s ^= i);
+
+        Ok(seed)
+    }
+
+    /// `CTR_DRBG_Update`, using the first `seedlen` bytes of `provided`.
+    fn update(&mut self, provided: &[u8; MAX_SEED_LEN]) {
+        let seed_len = self.seed_len();
+        let mut temp = [0; MAX_SEED_LEN + BLOCK_LEN];
+
+        for chunk in temp[..seed_len].chunks_mut(BLOCK_LEN) {
+            increment(&mut self.v);
+
+            let mut block = self.v;
+            self.cipher.encrypt(&mut block);
+            chunk.copy_from_slice(&block[..chunk.len()]);
+        }
+
+        temp.iter_mut()
+            .zip(&provided[..seed_len])
+            .for_each(|(t, p)| *t ^= p);
+
+        let key_len = key_len(self.algorithm);
+
+        self.cipher = Cipher::new(self.algorithm, &temp[..key_len]);
+        self.v.copy_from_slice(&temp[key_len..seed_len]);
+    }
+
+    /// `Block_Cipher_df`, writing `seedlen` bytes into `out`.
+    fn df(&self, inputs: &[&[u8]], out: &mut [u8; MAX_SEED_LEN]) {
+        let seed_len = self.seed_len();
+        let key_len = key_len(self.algorithm);
+
+        let input_len: usize = inputs.iter().map(|input| input.len()).sum();
+        let mut header = [0; 8];
+        header[..4].copy_from_slice(&(input_len as u32).to_be_bytes());
+        header[4..].copy_from_slice(&(seed_len as u32).to_be_bytes());
+
+        let mut key = [0; 32];
+        key.iter_mut().enumerate().for_each(|(i, k)| *k = i as u8);
+        let cipher = Cipher::new(self.algorithm, &key[..key_len]);
+
+        let mut temp = [0; MAX_SEED_LEN + BLOCK_LEN];
+
+        for (i, chunk) in temp[..seed_len].chunks_mut(BLOCK_LEN).enumerate() {
+            let mut iv = [0; BLOCK_LEN];
+            iv[..4].copy_from_slice(&(i as u32).to_be_bytes());
+
+            let mut parts: [&[u8]; 6] = [&iv, &header, &[], &[], &[], &[0x80]];
+            parts[2..2 + inputs.len()].copy_from_slice(inputs);
+
+            let block = bcc(&cipher, &parts);
+            chunk.copy_from_slice(&block[..chunk.len()]);
+        }
+
+        let cipher = Cipher::new(self.algorithm, &temp[..key_len]);
+        let mut x = [0; BLOCK_LEN];
+        x.copy_from_slice(&temp[key_len..seed_len]);
+
+        for chunk in out[..seed_len].chunks_mut(BLOCK_LEN) {
+            cipher.encrypt(&mut x);
+            chunk.copy_from_slice(&x[..chunk.len()]);
+        }
+    }
+}
+
+/// The `BCC` chaining function over the concatenation of `parts`, padding the
+/// final block with zeros.
+fn bcc(cipher: &Cipher, parts: &[&[u8]]) -> [u8; BLOCK_LEN] {
+    let mut chain = [0; BLOCK_LEN];
+    let mut pos = 0;
+
+    for &byte in parts.iter().flat_map(|part| part.iter()) {
+        chain[pos] ^= byte;
+        pos += 1;
+
+        if pos == BLOCK_LEN {
+            cipher.encrypt(&mut chain);
+            pos = 0;
+        }
+    }
+
+    if pos != 0 {
+        cipher.encrypt(&mut chain);
+    }
+
+    chain
+}
+
+/// Increment a big-endian block counter.
+#[inline]
+fn increment(block: &mut [u8; BLOCK_LEN]) {
+    *block = u128::from_be_bytes(*block).wrapping_add(1).to_be_bytes();
+}
@@ -0,0 +1,193 @@
+use super::{add_be, Algorithm, MAX_OUT_LEN, MAX_SEED_LEN};
+use sha2::{Digest, Sha224, Sha256, Sha384, Sha512, Sha512_224, Sha512_256};
+
+/// A hash function selected at runtime.
+#[derive(Clone)]
+pub(super) enum Hasher {
+    Sha224(Sha224),
+    Sha512_224(Sha512_224),
+    Sha256(Sha256),
+    Sha512_256(Sha512_256),
+    Sha384(Sha384),
+    Sha512(Sha512),
+}
+
+macro_rules! dispatch {
+    ($hasher:expr, $inner:ident => $body:expr) => {
+        match $hasher {
+            Hasher::Sha224($inner) => $body,
+            Hasher::Sha512_224($inner) => $body,
+            Hasher::Sha256($inner) => $body,
+            Hasher::Sha512_256($inner) => $body,
+            Hasher::Sha384($inner) => $body,
+            Hasher::Sha512($inner) => $body,
+        }
+    };
+}
+
+impl Hasher {
+    pub(super) fn new(algorithm: Algorithm) -> Hasher {
+        match algorithm {
+            Algorithm::Sha224 => Hasher::Sha224(Sha224::new()),
+            Algorithm::Sha512_224 => Hasher::Sha512_224(Sha512_224::new()),
+            Algorithm::Sha256 => Hasher::Sha256(Sha256::new()),
+            Algorithm::Sha512_256 => Hasher::Sha512_256(Sha512_256::new()),
+            Algorithm::Sha384 => Hasher::Sha384(Sha384::new()),
+            Algorithm::Sha512 => Hasher::Sha512(Sha512::new()),
+            _ => unreachable!("{} is not a hash function", algorithm.name()),
+        }
+    }
+
+    #[inline]
+    pub(super) fn update(&mut self, data: &[u8]) {
+        dispatch!(self, hasher => hasher.update(data))
+    }
+
+    /// Write the digest into `out`, returning the digest length.
+    #[inline]
+    pub(super) fn finalize_into(self, out: &mut [u8; MAX_OUT_LEN]) -> usize {
+        dispatch!(self, hasher => {
+            let digest = hasher.finalize();
+            out[..digest.len()].copy_from_slice(&digest);
+
+            digest.len()
+        })
+    }
+}
+
+/// The output length of a hash function, in bytes.
+#[inline]
+pub(super) const fn out_len(algorithm: Algorithm) -> usize {
+    match algorithm {
+        Algorithm::Sha224 | Algorithm::Sha512_224 => 28,
+        Algorithm::Sha256 | Algorithm::Sha512_256 => 32,
+        Algorithm::Sha384 => 48,
+        _ => 64,
+    }
+}
+
+/// The `seedlen` of `Hash_DRBG`, in bytes.
+#[inline]
+const fn seed_len(algorithm: Algorithm) -> usize {
+    match algorithm {
+        Algorithm::Sha384 | Algorithm::Sha512 => 111,
+        _ => 55,
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct HashDrbg {
+    algorithm: Algorithm,
+    v: [u8; MAX_SEED_LEN],
+    c: [u8; MAX_SEED_LEN],
+    seed_len: usize,
+    reseed_counter: u64,
+}
+
+impl HashDrbg {
+    pub(super) fn instantiate(
+        algorithm: Algorithm,
+        entropy: &[u8],
+        nonce: &[u8],
+        personalization: Option<&[u8]>,
+    ) -> HashDrbg {
+        let mut drbg = HashDrbg {
+            algorithm,
+            v: [0; MAX_SEED_LEN],
+            c: [0; MAX_SEED_LEN],
+            seed_len: seed_len(algorithm),
+            reseed_counter: 1,
+        };
+
+        let mut v = [0; MAX_SEED_LEN];
+        hash_df(
+            algorithm,
+            &mut v[..drbg.seed_len],
+            &[entropy, nonce, personalization.unwrap_or_default()],
+        );
+        drbg.set_v(&v);
+
+        drbg
+    }
+
+    pub(super) fn reseed(&mut self, entropy: &[u8], additional_input: Option<&[u8]>) {
+        let mut v = [0; MAX_SEED_LEN];
+        hash_df(
+            self.algorithm,
+            &mut v[..self.seed_len],
+            &[
+                &[0x01],
+                self.v(),
+                entropy,
+                additional_input.unwrap_or_default(),
+            ],
+        );
+        self.set_v(&v);
+    }
+
+    pub(super) fn generate(&mut self, bytes: &mut [u8], additional_input: Option<&[u8]>) {
+        let mut digest = [0; MAX_OUT_LEN];
+
+        if let Some(input) = additional_input {
+            let len = self.hash(&[&[0x02], self.v(), input], &mut digest);
+            add_be(&mut self.v[..self.seed_len], &digest[..len]);
+        }
+
+        let mut data = self.v;
+
+        for chunk in bytes.chunks_mut(out_len(self.algorithm)) {
+            self.hash(&[&data[..self.seed_len]], &mut digest);
+            chunk.copy_from_slice(&digest[..chunk.len()]);
+            add_be(&mut data[..self.seed_len], &[0x01]);
+        }
+
+        let len = self.hash(&[&[0x03], self.v()], &mut digest);
+        let seed_len = self.seed_len;
+
+        add_be(&mut self.v[..seed_len], &digest[..len]);
+        add_be(&mut self.v[..seed_len], &self.c[..seed_len]);
+        add_be(&mut self.v[..seed_len], &self.reseed_counter.to_be_bytes());
+
+        self.reseed_counter += 1;
+    }
+
+    #[inline]
+    fn v(&self) -> &[u8] {
+        &self.v[..self.seed_len]
+    }
+
+    /// Set `V` and derive `C` from it, resetting the reseed counter.
+    fn set_v(&mut self, v: &[u8; MAX_SEED_LEN]) {
+        self.v = *v;
+        hash_df(
+            self.algorithm,
+            &mut self.c[..self.seed_len],
+            &[&[0x00], &v[..self.seed_len]],
+        );
+        self.reseed_counter = 1;
+    }
+
+    #[inline]
+    fn hash(&self, inputs: &[&[u8]], out: &mut [u8; MAX_OUT_LEN]) -> usize {
+        let mut hasher = Hasher::new(self.algorithm);
+        inputs.iter().for_each(|input| hasher.update(input));
+        hasher.finalize_into(out)
+    }
+}
+
+/// The `Hash_df` derivation function.
+fn hash_df(algorithm: Algorithm, out: &mut [u8], inputs: &[&[u8]]) {
+    let bits = (out.len() as u32 * 8).to_be_bytes();
+    let mut digest = [0; MAX_OUT_LEN];
+
+    for (counter, chunk) in out.chunks_mut(out_len(algorithm)).enumerate() {
+        let mut hasher = Hasher::new(algorithm);
+
+        hasher.update(&[counter as u8 + 1]);
+        hasher.update(&bits);
+        inputs.iter().for_each(|input| hasher.update(input));
+
+        hasher.finalize_into(&mut digest);
+        chunk.copy_from_slice(&digest[..chunk.len()]);
+    }
+}
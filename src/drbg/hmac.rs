@@ -0,0 +1,122 @@
+use super::{hash::out_len, Algorithm, MAX_OUT_LEN};
+use hmac::{Hmac, Mac as _};
+use sha2::{Sha224, Sha256, Sha384, Sha512, Sha512_224, Sha512_256};
+
+/// Compute `HMAC(key, inputs...)` with the hash function selected at runtime.
+fn mac(algorithm: Algorithm, key: &[u8], inputs: &[&[u8]], out: &mut [u8; MAX_OUT_LEN]) {
+    macro_rules! mac {
+        ($hash:ty) => {{
+            let mut mac =
+                Hmac::<$hash>::new_from_slice(key).expect("HMAC accepts keys of any length");
+            inputs.iter().for_each(|input| mac.update(input));
+
+            let tag = mac.finalize().into_bytes();
+            out[..tag.len()].copy_from_slice(&tag);
+        }};
+    }
+
+    match algorithm {
+        Algorithm::Sha224 => mac!(Sha224),
+        Algorithm::Sha512_224 => mac!(Sha512_224),
+        Algorithm::Sha256 => mac!(Sha256),
+        Algorithm::Sha512_256 => mac!(Sha512_256),
+        Algorithm::Sha384 => mac!(Sha384),
+        Algorithm::Sha512 => mac!(Sha512),
+        _ => unreachable!("{} is not a hash function", algorithm.name()),
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct HmacDrbg {
+    algorithm: Algorithm,
+    k: [u8; MAX_OUT_LEN],
+    v: [u8; MAX_OUT_LEN],
+    out_len: usize,
+}
+
+impl HmacDrbg {
+    pub(super) fn instantiate(
+        algorithm: Algorithm,
+        entropy: &[u8],
+        nonce: &[u8],
+        personalization: Option<&[u8]>,
+    ) -> HmacDrbg {
+        let mut drbg = HmacDrbg {
+            algorithm,
+            k: [0x00; MAX_OUT_LEN],
+            v: [0x01; MAX_OUT_LEN],
+            out_len: out_len(algorithm),
+        };
+
+        match personalization {
+            Some(personalization) => drbg.update(&[entropy, nonce, personalization]),
+            None => drbg.update(&[entropy, nonce]),
+        }
+
+        drbg
+    }
+
+    pub(super) fn reseed(&mut self, entropy: &[u8], additional_input: Option<&[u8]>) {
+        match additional_input {
+            Some(input) => self.update(&[entropy, input]),
+            None => self.update(&[entropy]),
+        }
+    }
+
+    pub(super) fn generate(&mut self, bytes: &mut [u8], additional_input: Option<&[u8]>) {
+        if let Some(input) = additional_input {
+            self.update(&[input]);
+        }
+
+        for chunk in bytes.chunks_mut(self.out_len) {
+            self.next_v();
+            chunk.copy_from_slice(&self.v[..chunk.len()]);
+        }
+
+        match additional_input {
+            Some(input) => self.update(&[input]),
+            None => self.update(&[]),
+        }
+    }
+
+    /// `HMAC_DRBG_Update`.
+    ///
+    /// Like Java, the second round is skipped only when there are no inputs
+    /// at all, not when the inputs are empty.
+    fn update(&mut self, inputs: &[&[u8]]) {
+        self.update_round(0x00, inputs);
+
+        if !inputs.is_empty() {
+            self.update_round(0x01, inputs);
+        }
+    }
+
+    fn update_round(&mut self, round: u8, inputs: &[&[u8]]) {
+        let mut k = [0; MAX_OUT_LEN];
+        let mut data: [&[u8]; 5] = [&self.v[..self.out_len], &[round], &[], &[], &[]];
+
+        data[2..2 + inputs.len()].copy_from_slice(inputs);
+        mac(
+            self.algorithm,
+            &self.k[..self.out_len],
+            &data[..2 + inputs.len()],
+            &mut k,
+        );
+
+        self.k = k;
+        self.next_v();
+    }
+
+    #[inline]
+    fn next_v(&mut self) {
+        let mut v = [0; MAX_OUT_LEN];
+        mac(
+            self.algorithm,
+            &self.k[..self.out_len],
+            &[&self.v[..self.out_len]],
+            &mut v,
+        );
+
+        self.v = v;
+    }
+}
@@ -1,23 +1,331 @@
-use core::sync::atomic::{AtomicI64, Ordering};
+use core::fmt;
+
+use crate::atomic::{AtomicI64, Ordering};
 
 #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
 pub mod x86;
 
-pub type NextI64 = fn() -> i64;
+#[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+pub mod riscv;
+
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+pub mod wasm;
+
+#[cfg(windows)]
+pub mod windows;
+
+#[cfg(feature = "async")]
+mod asynchronous;
+#[cfg(feature = "async")]
+pub use asynchronous::{AsyncEntropySource, FromSync};
+
+#[cfg(feature = "embedded-hal")]
+mod hal;
+#[cfg(feature = "embedded-hal")]
+pub use hal::HalRng;
+
+mod health;
+pub use health::HealthChecked;
+
+mod pool;
+pub use pool::EntropyPool;
+
+#[cfg(feature = "std")]
+mod seed_file;
+#[cfg(feature = "std")]
+pub use seed_file::SeedFileSource;
+
+/// Errors reported by an [`EntropySource`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum EntropyError {
+    /// The source does not exist on this machine or in this environment.
+    Unsupported,
+    /// The source did not have entropy ready in time, such as `rdrand` or
+    /// `rdseed` running out of retries.
+    NotReady,
+    /// The source reported that it has failed.
+    Failed,
+    /// The operating system reported an error, with its error code.
+    Os(i32),
+    /// The source failed a health test, see
+    /// [`HealthChecked`](crate::entropy::HealthChecked).
+    HealthTest,
+}
+
+impl fmt::Display for EntropyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EntropyError::Unsupported => f.write_str("entropy source is not supported"),
+            EntropyError::NotReady => f.write_str("entropy source was not ready"),
+            EntropyError::Failed => f.write_str("entropy source failed"),
+            EntropyError::Os(code) => write!(f, "entropy source failed with OS error {code}"),
+            EntropyError::HealthTest => f.write_str("entropy source failed a health test"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EntropyError {}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for EntropyError {
+    fn from(error: std::io::Error) -> EntropyError {
+        error
+            .raw_os_error()
+            .map_or(EntropyError::Failed, EntropyError::Os)
+    }
+}
+
+/// A source of entropy to seed random number generators with.
+///
+/// Sources are read through `&mut self`, so they can carry state of their
+/// own, such as an open file, a counter or a hardware peripheral.
+pub trait EntropySource {
+    /// Fill `bytes` with entropy.
+    fn try_fill(&mut self, bytes: &mut [u8]) -> Result<(), EntropyError>;
+
+    /// Get an `i64` of entropy, by default from 8 bytes of
+    /// [`EntropySource::try_fill`] in little-endian order.
+    #[inline]
+    fn try_next_i64(&mut self) -> Result<i64, EntropyError> {
+        let mut bytes = [0; 8];
+
+        self.try_fill(&mut bytes)?;
+
+        Ok(i64::from_le_bytes(bytes))
+    }
+
+    /// Like [`EntropySource::try_next_i64`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the source fails.
+    #[inline]
+    fn next_i64(&mut self) -> i64 {
+        match self.try_next_i64() {
+            Ok(x) => x,
+            Err(error) => panic!("{error}"),
+        }
+    }
 
-pub trait EntropySource: Sized {
-    fn get_entropy(self) -> NextI64;
+    /// Fall back to `other` whenever this source fails, see [`Chain`].
+    #[inline]
+    fn or<B: EntropySource>(self, other: B) -> Chain<Self, B>
+    where
+        Self: Sized,
+    {
+        Chain(self, other)
+    }
+}
+
+/// Fill `bytes` with the little-endian bytes of successive `i64`s from
+/// `next`, as sources producing whole integers do.
+#[inline]
+pub fn fill_with_i64s(
+    bytes: &mut [u8],
+    mut next: impl FnMut() -> Result<i64, EntropyError>,
+) -> Result<(), EntropyError> {
+    for chunk in bytes.chunks_mut(8) {
+        let x = next()?.to_le_bytes();
+
+        chunk.copy_from_slice(&x[..chunk.len()]);
+    }
+
+    Ok(())
+}
+
+impl<S: EntropySource + ?Sized> EntropySource for &mut S {
+    #[inline]
+    fn try_fill(&mut self, bytes: &mut [u8]) -> Result<(), EntropyError> {
+        (**self).try_fill(bytes)
+    }
+
+    #[inline]
+    fn try_next_i64(&mut self) -> Result<i64, EntropyError> {
+        (**self).try_next_i64()
+    }
 }
 
+#[cfg(feature = "std")]
+impl<S: EntropySource + ?Sized> EntropySource for std::boxed::Box<S> {
+    #[inline]
+    fn try_fill(&mut self, bytes: &mut [u8]) -> Result<(), EntropyError> {
+        (**self).try_fill(bytes)
+    }
+
+    #[inline]
+    fn try_next_i64(&mut self) -> Result<i64, EntropyError> {
+        (**self).try_next_i64()
+    }
+}
+
+/// Entropy source calling a closure for each `i64`, created with
+/// [`from_fn`].
+#[derive(Debug, Clone, Copy)]
+pub struct FromFn<F>(F);
+
+/// Create an entropy source from a closure returning an `i64` of entropy,
+/// which may capture whatever state it needs.
+#[inline]
+pub fn from_fn<F: FnMut() -> i64>(f: F) -> FromFn<F> {
+    FromFn(f)
+}
+
+impl<F: FnMut() -> i64> EntropySource for FromFn<F> {
+    #[inline]
+    fn try_fill(&mut self, bytes: &mut [u8]) -> Result<(), EntropyError> {
+        fill_with_i64s(bytes, || Ok((self.0)()))
+    }
+
+    #[inline]
+    fn try_next_i64(&mut self) -> Result<i64, EntropyError> {
+        Ok((self.0)())
+    }
+}
+
+/// Entropy source of a plain function, kept from when sources could only be
+/// function pointers. Use [`from_fn`] for closures.
+pub type NextI64 = fn() -> i64;
+
 impl EntropySource for NextI64 {
-    fn get_entropy(self) -> NextI64 {
-        self
+    #[inline]
+    fn try_fill(&mut self, bytes: &mut [u8]) -> Result<(), EntropyError> {
+        fill_with_i64s(bytes, || Ok(self()))
+    }
+
+    #[inline]
+    fn try_next_i64(&mut self) -> Result<i64, EntropyError> {
+        Ok(self())
     }
 }
 
+/// Entropy source of nothing but zeros.
 impl EntropySource for () {
-    fn get_entropy(self) -> NextI64 {
-        || 0
+    #[inline]
+    fn try_fill(&mut self, bytes: &mut [u8]) -> Result<(), EntropyError> {
+        bytes.fill(0);
+
+        Ok(())
+    }
+}
+
+/// Entropy source combining two others by XORing their outputs, the way
+/// Java mixes its seed uniquifier with the nanosecond time.
+///
+/// Use [`mix!`](crate::mix) to combine more than two. Either source
+/// failing fails the mix.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Mix<A, B>(pub A, pub B);
+
+impl<A: EntropySource, B: EntropySource> EntropySource for Mix<A, B> {
+    fn try_fill(&mut self, bytes: &mut [u8]) -> Result<(), EntropyError> {
+        self.0.try_fill(bytes)?;
+
+        let mut buf = [0; 64];
+
+        for chunk in bytes.chunks_mut(buf.len()) {
+            let buf = &mut buf[..chunk.len()];
+
+            self.1.try_fill(buf)?;
+            chunk.iter_mut().zip(&*buf).for_each(|(x, y)| *x ^= y);
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn try_next_i64(&mut self) -> Result<i64, EntropyError> {
+        Ok(self.0.try_next_i64()? ^ self.1.try_next_i64()?)
+    }
+}
+
+/// Combine entropy sources with [`Mix`](crate::entropy::Mix), nesting them
+/// from the right, so `mix!(a, b, c)` is `Mix(a, Mix(b, c))`.
+#[macro_export]
+macro_rules! mix {
+    ($source:expr $(,)?) => {
+        $source
+    };
+    ($source:expr, $($rest:expr),+ $(,)?) => {
+        $crate::entropy::Mix($source, $crate::mix!($($rest),+))
+    };
+}
+
+/// The odd integer closest to 2<sup>64</sup> over the golden ratio, the
+/// gamma of SplitMix64.
+const GOLDEN_GAMMA: u64 = 0x9E37_79B9_7F4A_7C15;
+
+/// The SplitMix64 finalizer, `mix64` in Java's `SplittableRandom`.
+#[inline]
+#[must_use]
+const fn mix64(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Entropy source passing every `i64` of another through the SplitMix64
+/// finalizer, spreading weak sources like timers, which only change in
+/// their low bits, over all 64 bits.
+///
+/// Each sample is offset by a multiple of the golden ratio first, as
+/// SplitMix64 does with its state, so a source that repeats or is stuck at
+/// zero still gives varying values. This improves how the output looks,
+/// not how hard it is to guess: it adds no entropy.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Conditioned<S> {
+    source: S,
+    gamma: u64,
+}
+
+impl<S> Conditioned<S> {
+    #[inline]
+    #[must_use]
+    pub const fn new(source: S) -> Conditioned<S> {
+        Conditioned { source, gamma: 0 }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> S {
+        self.source
+    }
+}
+
+impl<S: EntropySource> EntropySource for Conditioned<S> {
+    #[inline]
+    fn try_fill(&mut self, bytes: &mut [u8]) -> Result<(), EntropyError> {
+        fill_with_i64s(bytes, || self.try_next_i64())
+    }
+
+    #[inline]
+    fn try_next_i64(&mut self) -> Result<i64, EntropyError> {
+        let sample = self.source.try_next_i64()? as u64;
+
+        self.gamma = self.gamma.wrapping_add(GOLDEN_GAMMA);
+
+        Ok(mix64(sample.wrapping_add(self.gamma)) as i64)
+    }
+}
+
+/// Entropy source reading the first of two sources, and the second if the
+/// first fails, created with [`EntropySource::or`].
+///
+/// Chains nest, so `rdseed.or(rdrand).or(NanosecondSource)` tries each in
+/// turn, and fails only with the error of the last.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Chain<A, B>(pub A, pub B);
+
+impl<A: EntropySource, B: EntropySource> EntropySource for Chain<A, B> {
+    #[inline]
+    fn try_fill(&mut self, bytes: &mut [u8]) -> Result<(), EntropyError> {
+        self.0.try_fill(bytes).or_else(|_| self.1.try_fill(bytes))
+    }
+
+    #[inline]
+    fn try_next_i64(&mut self) -> Result<i64, EntropyError> {
+        self.0.try_next_i64().or_else(|_| self.1.try_next_i64())
     }
 }
 
@@ -26,19 +334,57 @@ pub struct StaticSource;
 
 static STATIC_ENTROPY: AtomicI64 = AtomicI64::new(0);
 
+#[cfg(feature = "std")]
+std::thread_local! {
+    static SCOPED_ENTROPY: core::cell::Cell<Option<i64>> = const { core::cell::Cell::new(None) };
+}
+
 impl StaticSource {
+    /// Get the static entropy, or the value set by the innermost
+    /// [`StaticSource::with_value`] on this thread.
     pub fn get() -> i64 {
+        #[cfg(feature = "std")]
+        if let Some(value) = SCOPED_ENTROPY.with(|scoped| scoped.get()) {
+            return value;
+        }
+
         STATIC_ENTROPY.load(Ordering::Relaxed)
     }
 
+    /// Set the static entropy for every thread, returning the old value.
     pub fn set(value: i64) -> i64 {
         STATIC_ENTROPY.swap(value, Ordering::Relaxed)
     }
+
+    /// Run `f` with the static entropy set to `value` on this thread only,
+    /// so tests running in parallel can each pin their own.
+    ///
+    /// The previous value is restored afterwards, even if `f` panics.
+    #[cfg(feature = "std")]
+    pub fn with_value<R>(value: i64, f: impl FnOnce() -> R) -> R {
+        struct Restore(Option<i64>);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                SCOPED_ENTROPY.with(|scoped| scoped.set(self.0));
+            }
+        }
+
+        let _restore = Restore(SCOPED_ENTROPY.with(|scoped| scoped.replace(Some(value))));
+
+        f()
+    }
 }
 
 impl EntropySource for StaticSource {
-    fn get_entropy(self) -> NextI64 {
-        StaticSource::get
+    #[inline]
+    fn try_fill(&mut self, bytes: &mut [u8]) -> Result<(), EntropyError> {
+        fill_with_i64s(bytes, || Ok(StaticSource::get()))
+    }
+
+    #[inline]
+    fn try_next_i64(&mut self) -> Result<i64, EntropyError> {
+        Ok(StaticSource::get())
     }
 }
 
@@ -46,19 +392,418 @@ impl EntropySource for StaticSource {
 #[derive(Debug, Clone, Copy, Default)]
 pub struct NanosecondSource;
 
+#[cfg(feature = "std")]
+impl NanosecondSource {
+    /// Get the nanoseconds since the Unix epoch.
+    pub fn get() -> i64 {
+        use ::std::time::SystemTime;
+        let time = SystemTime::now();
+
+        let duration = match time.duration_since(SystemTime::UNIX_EPOCH) {
+            Ok(duration) => duration,
+            Err(error) => error.duration(),
+        };
+
+        // We do not really care if it's lossy at this point.
+        duration.as_nanos() as i64
+    }
+}
+
+#[cfg(feature = "std")]
 impl EntropySource for NanosecondSource {
-    fn get_entropy(self) -> NextI64 {
-        || {
-            use ::std::time::SystemTime;
-            let time = SystemTime::now();
-
-            let duration = match time.duration_since(SystemTime::UNIX_EPOCH) {
-                Ok(duration) => duration,
-                Err(error) => error.duration(),
-            };
-
-            // We do not really care if it's lossy at this point.
-            duration.as_nanos() as i64
+    #[inline]
+    fn try_fill(&mut self, bytes: &mut [u8]) -> Result<(), EntropyError> {
+        fill_with_i64s(bytes, || Ok(NanosecondSource::get()))
+    }
+
+    #[inline]
+    fn try_next_i64(&mut self) -> Result<i64, EntropyError> {
+        Ok(NanosecondSource::get())
+    }
+}
+
+/// Entropy source using the monotonic clock, as the nanoseconds elapsed
+/// since it was first read, mixed with a counter of reads.
+///
+/// Unlike [`NanosecondSource`], it never goes backwards or repeats when the
+/// wall clock is adjusted, and the counter keeps two reads in the same tick
+/// apart, so its values are unique within the process. The elapsed time is
+/// offset by the wall clock time of the first read, so that processes do
+/// not all start from zero. They are still easy to guess, and processes
+/// started in the same nanosecond can share values, so mix it with
+/// [`ProcessSource`] where that matters.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InstantSource;
+
+#[cfg(feature = "std")]
+impl InstantSource {
+    /// Get the next value.
+    pub fn get() -> i64 {
+        use std::{
+            sync::OnceLock,
+            time::{Instant, SystemTime, UNIX_EPOCH},
+        };
+
+        static START: OnceLock<(Instant, i64)> = OnceLock::new();
+        static COUNTER: AtomicI64 = AtomicI64::new(0);
+
+        let (start, base) = *START.get_or_init(|| {
+            let base = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |duration| duration.as_nanos() as i64);
+
+            (Instant::now(), base)
+        });
+        let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+        let nanos = base.wrapping_add(start.elapsed().as_nanos() as i64);
+
+        // The counter is spread over all the bits by the golden ratio, so
+        // it reaches beyond the low bits the clock changes.
+        nanos ^ count.wrapping_mul(0x9E37_79B9_7F4A_7C15_u64 as i64)
+    }
+}
+
+#[cfg(feature = "std")]
+impl EntropySource for InstantSource {
+    #[inline]
+    fn try_fill(&mut self, bytes: &mut [u8]) -> Result<(), EntropyError> {
+        fill_with_i64s(bytes, || Ok(InstantSource::get()))
+    }
+
+    #[inline]
+    fn try_next_i64(&mut self) -> Result<i64, EntropyError> {
+        Ok(InstantSource::get())
+    }
+}
+
+/// Entropy source hashing the identity of the calling process and thread:
+/// the process id, the thread id, and the addresses of a static, the stack
+/// and a function, which differ between runs where the address space is
+/// randomized.
+///
+/// Workers forked or spawned at the same moment get the same time from the
+/// clock sources, so mixing this in with [`Mix`] tells their seeds apart.
+/// By itself it has next to no entropy, and repeats within a thread.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProcessSource;
+
+#[cfg(feature = "std")]
+impl ProcessSource {
+    /// Get the hash of the caller's identity.
+    pub fn get() -> i64 {
+        use std::{
+            hash::{DefaultHasher, Hash, Hasher},
+            process, ptr, thread,
+        };
+
+        static ANCHOR: u8 = 0;
+        let local = 0u8;
+
+        let mut hasher = DefaultHasher::new();
+
+        process::id().hash(&mut hasher);
+        thread::current().id().hash(&mut hasher);
+        ptr::addr_of!(ANCHOR).hash(&mut hasher);
+        ptr::addr_of!(local).hash(&mut hasher);
+        (ProcessSource::get as fn() -> i64 as usize).hash(&mut hasher);
+
+        hasher.finish() as i64
+    }
+}
+
+#[cfg(feature = "std")]
+impl EntropySource for ProcessSource {
+    #[inline]
+    fn try_fill(&mut self, bytes: &mut [u8]) -> Result<(), EntropyError> {
+        fill_with_i64s(bytes, || Ok(ProcessSource::get()))
+    }
+
+    #[inline]
+    fn try_next_i64(&mut self) -> Result<i64, EntropyError> {
+        Ok(ProcessSource::get())
+    }
+}
+
+/// Entropy source backed by the operating system's CSPRNG through the
+/// `getrandom` crate.
+#[cfg(feature = "getrandom")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GetRandomSource;
+
+#[cfg(feature = "getrandom")]
+impl EntropySource for GetRandomSource {
+    #[inline]
+    fn try_fill(&mut self, bytes: &mut [u8]) -> Result<(), EntropyError> {
+        getrandom::getrandom(bytes).map_err(|error| match error.raw_os_error() {
+            Some(code) => EntropyError::Os(code),
+            None if error == getrandom::Error::UNSUPPORTED => EntropyError::Unsupported,
+            None => EntropyError::Failed,
+        })
+    }
+}
+
+/// Entropy source reading `/dev/urandom` directly.
+///
+/// The file is opened the first time it is read, and kept open from then
+/// on, so it keeps working if the process later loses access to `/dev`.
+#[cfg(all(feature = "std", unix))]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DevUrandom;
+
+#[cfg(all(feature = "std", unix))]
+impl DevUrandom {
+    /// The path read from.
+    pub const PATH: &'static str = "/dev/urandom";
+
+    /// Fill `bytes` from `/dev/urandom`, opening it if this is the first
+    /// read.
+    pub fn try_fill_bytes(bytes: &mut [u8]) -> std::io::Result<()> {
+        use std::{fs::File, io::Read, sync::OnceLock};
+
+        static FILE: OnceLock<File> = OnceLock::new();
+
+        let mut file = match FILE.get() {
+            Some(file) => file,
+            None => {
+                let file = File::open(DevUrandom::PATH)?;
+
+                // If another thread opened it first, theirs is kept and this
+                // one closed.
+                FILE.get_or_init(|| file)
+            }
+        };
+
+        file.read_exact(bytes)
+    }
+}
+
+#[cfg(all(feature = "std", unix))]
+impl EntropySource for DevUrandom {
+    #[inline]
+    fn try_fill(&mut self, bytes: &mut [u8]) -> Result<(), EntropyError> {
+        Ok(DevUrandom::try_fill_bytes(bytes)?)
+    }
+}
+
+/// Entropy source reading any byte stream, such as a device file, a socket
+/// to an entropy daemon, or a fixture file in tests.
+///
+/// Every read fills its buffer completely, so reads fail once the stream
+/// ends.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Default)]
+pub struct ReaderSource<R>(R);
+
+#[cfg(feature = "std")]
+impl<R: std::io::Read> ReaderSource<R> {
+    #[inline]
+    #[must_use]
+    pub const fn new(reader: R) -> ReaderSource<R> {
+        ReaderSource(reader)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn get_ref(&self) -> &R {
+        &self.0
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.0
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> R {
+        self.0
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: std::io::Read> EntropySource for ReaderSource<R> {
+    #[inline]
+    fn try_fill(&mut self, bytes: &mut [u8]) -> Result<(), EntropyError> {
+        Ok(self.0.read_exact(bytes)?)
+    }
+}
+
+/// Entropy source reading a fixed seed from the `JRAND_SEED` environment
+/// variable, and otherwise falling back to another source.
+///
+/// This lets a whole test suite be pinned to a reproducible seed, say from
+/// CI, without passing seeds to every test. The variable is parsed as a
+/// decimal `i64`, or as hexadecimal with a `0x` prefix, and is read on every
+/// use, so every value it gives is the same seed.
+///
+/// A variable that is set but cannot be parsed fails the read, rather than
+/// silently falling back.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EnvSeedSource<S>(pub S);
+
+#[cfg(feature = "std")]
+impl<S> EnvSeedSource<S> {
+    /// The environment variable read.
+    pub const VAR: &'static str = "JRAND_SEED";
+
+    /// Get the seed from the environment, `None` if it is unset, or an
+    /// error if it is not an `i64`.
+    pub fn seed() -> Option<Result<i64, EntropyError>> {
+        let var = std::env::var(EnvSeedSource::<S>::VAR).ok()?;
+        let var = var.trim();
+
+        let seed = match var.strip_prefix("0x").or_else(|| var.strip_prefix("0X")) {
+            Some(hex) => u64::from_str_radix(hex, 16).map(|seed| seed as i64),
+            None => var.parse(),
+        };
+
+        Some(seed.map_err(|_| EntropyError::Failed))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S: EntropySource> EntropySource for EnvSeedSource<S> {
+    #[inline]
+    fn try_fill(&mut self, bytes: &mut [u8]) -> Result<(), EntropyError> {
+        match EnvSeedSource::<S>::seed() {
+            Some(seed) => {
+                let seed = seed?;
+
+                fill_with_i64s(bytes, || Ok(seed))
+            }
+            None => self.0.try_fill(bytes),
         }
     }
+
+    #[inline]
+    fn try_next_i64(&mut self) -> Result<i64, EntropyError> {
+        match EnvSeedSource::<S>::seed() {
+            Some(seed) => seed,
+            None => self.0.try_next_i64(),
+        }
+    }
+}
+
+/// Entropy source reading the CPU's cycle counter, `rdtsc` on x86 and the
+/// virtual counter `cntvct_el0` on AArch64.
+///
+/// This is cheap and needs neither `std` nor an operating system, but it is
+/// not cryptographic: the counter only increases, so successive reads are
+/// close to each other, and anyone who can estimate how long the program
+/// has run can guess them. It is only better than a fixed seed.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64"))]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CycleCounterSource;
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64"))]
+impl CycleCounterSource {
+    /// Read the cycle counter.
+    #[inline]
+    pub fn get() -> i64 {
+        cfg_if::cfg_if! {
+            if #[cfg(target_arch = "x86_64")] {
+                // SAFETY: Every x86_64 CPU has `rdtsc`.
+                unsafe { core::arch::x86_64::_rdtsc() as i64 }
+            } else if #[cfg(target_arch = "x86")] {
+                // SAFETY: Every x86 CPU Rust supports has `rdtsc`.
+                unsafe { core::arch::x86::_rdtsc() as i64 }
+            } else {
+                let counter: u64;
+
+                // SAFETY: `cntvct_el0` is readable from every exception level
+                // unless the kernel deliberately traps it.
+                unsafe {
+                    core::arch::asm!(
+                        "mrs {}, cntvct_el0",
+                        out(reg) counter,
+                        options(nomem, nostack),
+                    );
+                }
+
+                counter as i64
+            }
+        }
+    }
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64"))]
+impl EntropySource for CycleCounterSource {
+    #[inline]
+    fn try_fill(&mut self, bytes: &mut [u8]) -> Result<(), EntropyError> {
+        fill_with_i64s(bytes, || Ok(CycleCounterSource::get()))
+    }
+
+    #[inline]
+    fn try_next_i64(&mut self) -> Result<i64, EntropyError> {
+        Ok(CycleCounterSource::get())
+    }
+}
+
+/// Entropy source using the best source available, probed each time it is
+/// read: `rdseed`, then `rdrand`, then the RISC-V `seed` CSR, then the
+/// operating system, then the nanosecond time, and finally
+/// [`StaticSource`].
+///
+/// Sources that are missing from the target, or disabled by features, are
+/// skipped, and a source that fails is passed over for the next one, so
+/// this never fails itself. Without anything better, it falls all the way
+/// back to the static entropy, which is zero unless set.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultSource;
+
+impl EntropySource for DefaultSource {
+    fn try_fill(&mut self, bytes: &mut [u8]) -> Result<(), EntropyError> {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            if let Some(mut source) = x86::RdSeed::new() {
+                if source.try_fill(bytes).is_ok() {
+                    return Ok(());
+                }
+            }
+
+            if let Some(mut source) = x86::RdRand::new() {
+                if source.try_fill(bytes).is_ok() {
+                    return Ok(());
+                }
+            }
+        }
+
+        #[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+        if let Some(mut source) = riscv::Zkr::new() {
+            if source.try_fill(bytes).is_ok() {
+                return Ok(());
+            }
+        }
+
+        #[cfg(feature = "getrandom")]
+        if GetRandomSource.try_fill(bytes).is_ok() {
+            return Ok(());
+        }
+
+        #[cfg(all(feature = "std", unix))]
+        if DevUrandom.try_fill(bytes).is_ok() {
+            return Ok(());
+        }
+
+        #[cfg(windows)]
+        if windows::BCrypt.try_fill(bytes).is_ok() {
+            return Ok(());
+        }
+
+        #[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+        if wasm::WebCrypto.try_fill(bytes).is_ok() {
+            return Ok(());
+        }
+
+        #[cfg(feature = "std")]
+        if NanosecondSource.try_fill(bytes).is_ok() {
+            return Ok(());
+        }
+
+        StaticSource.try_fill(bytes)
+    }
 }
@@ -0,0 +1,70 @@
+use super::{EntropyError, EntropySource};
+
+/// A source of entropy that is read asynchronously, such as a TRNG
+/// peripheral driven by interrupts or a service on the network.
+///
+/// Like [`EntropySource`], but awaited instead of blocking. Sync sources can
+/// be used where an async one is expected with [`FromSync`].
+// Executors on embedded are often single-threaded, so the futures are not
+// required to be `Send`.
+#[allow(async_fn_in_trait)]
+pub trait AsyncEntropySource {
+    /// Fill `bytes` with entropy.
+    async fn try_fill(&mut self, bytes: &mut [u8]) -> Result<(), EntropyError>;
+
+    /// Get an `i64` of entropy, by default from 8 bytes of
+    /// [`AsyncEntropySource::try_fill`] in little-endian order.
+    #[inline]
+    async fn try_next_i64(&mut self) -> Result<i64, EntropyError> {
+        let mut bytes = [0; 8];
+
+        self.try_fill(&mut bytes).await?;
+
+        Ok(i64::from_le_bytes(bytes))
+    }
+
+    /// Like [`AsyncEntropySource::try_next_i64`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the source fails.
+    #[inline]
+    async fn next_i64(&mut self) -> i64 {
+        match self.try_next_i64().await {
+            Ok(x) => x,
+            Err(error) => panic!("{error}"),
+        }
+    }
+}
+
+impl<S: AsyncEntropySource + ?Sized> AsyncEntropySource for &mut S {
+    #[inline]
+    async fn try_fill(&mut self, bytes: &mut [u8]) -> Result<(), EntropyError> {
+        (**self).try_fill(bytes).await
+    }
+
+    #[inline]
+    async fn try_next_i64(&mut self) -> Result<i64, EntropyError> {
+        (**self).try_next_i64().await
+    }
+}
+
+/// Async entropy source reading a sync one, which completes without ever
+/// yielding.
+///
+/// This suits sources that are fast, like `rdrand`, but one that blocks,
+/// like a file, still blocks the executor while it is read.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FromSync<S>(pub S);
+
+impl<S: EntropySource> AsyncEntropySource for FromSync<S> {
+    #[inline]
+    async fn try_fill(&mut self, bytes: &mut [u8]) -> Result<(), EntropyError> {
+        self.0.try_fill(bytes)
+    }
+
+    #[inline]
+    async fn try_next_i64(&mut self) -> Result<i64, EntropyError> {
+        self.0.try_next_i64()
+    }
+}
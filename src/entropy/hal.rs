@@ -0,0 +1,18 @@
+use embedded_hal::blocking::rng::Read;
+
+use super::{EntropyError, EntropySource};
+
+/// Entropy source reading a TRNG peripheral through its embedded-hal
+/// driver, the blocking `rng::Read` trait of embedded-hal 0.2.
+///
+/// The driver's errors only say that the peripheral failed, so they are
+/// reported as [`EntropyError::Failed`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HalRng<R>(pub R);
+
+impl<R: Read> EntropySource for HalRng<R> {
+    #[inline]
+    fn try_fill(&mut self, bytes: &mut [u8]) -> Result<(), EntropyError> {
+        self.0.read(bytes).map_err(|_| EntropyError::Failed)
+    }
+}
@@ -0,0 +1,190 @@
+use super::{EntropyError, EntropySource};
+
+/// How many samples make up a window of the adaptive proportion test, for
+/// samples that are not binary.
+const WINDOW: u32 = 512;
+
+/// How many samples are tested, and discarded, before the first output.
+const STARTUP_SAMPLES: usize = 1024;
+
+/// Entropy source running the continuous health tests of NIST SP 800-90B
+/// over the bytes of another source.
+///
+/// Every byte is a sample for the repetition count test, which fails when
+/// the same byte repeats too many times in a row, and for the adaptive
+/// proportion test, which fails when the first byte of a 512 byte window
+/// comes up too often in the rest of it. Before the first output, 1024
+/// bytes are tested and discarded as the startup test.
+///
+/// Once a test fails, every read fails with [`EntropyError::HealthTest`]
+/// until [`HealthChecked::reset`] is called, and the bytes of the failing
+/// read must not be used.
+#[derive(Debug, Clone)]
+pub struct HealthChecked<S> {
+    source: S,
+    repetition_cutoff: u32,
+    proportion_cutoff: u32,
+    started: bool,
+    failed: bool,
+    last: u8,
+    repetitions: u32,
+    reference: u8,
+    window: u32,
+    matches: u32,
+}
+
+impl<S> HealthChecked<S> {
+    /// The repetition count cutoff for a source of full entropy, with a
+    /// false positive rate of 2<sup>-40</sup>, the lowest the specification
+    /// allows, so that checking megabytes of output does not fail by chance.
+    pub const REPETITION_CUTOFF: u32 = 6;
+
+    /// The adaptive proportion cutoff for a source of full entropy, with a
+    /// false positive rate of 2<sup>-40</sup>.
+    pub const PROPORTION_CUTOFF: u32 = 19;
+
+    /// Check a source claimed to have full entropy, 8 bits per byte.
+    #[inline]
+    #[must_use]
+    pub const fn new(source: S) -> HealthChecked<S> {
+        HealthChecked::with_cutoffs(
+            source,
+            HealthChecked::<S>::REPETITION_CUTOFF,
+            HealthChecked::<S>::PROPORTION_CUTOFF,
+        )
+    }
+
+    /// Check a source with the cutoffs for its assessed min-entropy `H`
+    /// per byte.
+    ///
+    /// For a false positive rate of 2<sup>-a</sup>, the specification gives
+    /// the repetition count cutoff as `1 + ceil(a / H)`, and the adaptive
+    /// proportion cutoff as `1 + CRITBINOM(512, 2^-H, 1 - 2^-a)`. With
+    /// `a = 40`, that is 336 for `H = 1`, 201 for `H = 2` and 78 for
+    /// `H = 4`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either cutoff is less than 2, since every sample would
+    /// fail.
+    #[inline]
+    #[must_use]
+    pub const fn with_cutoffs(
+        source: S,
+        repetition_cutoff: u32,
+        proportion_cutoff: u32,
+    ) -> HealthChecked<S> {
+        assert!(
+            repetition_cutoff >= 2,
+            "repetition cutoff must be at least 2"
+        );
+        assert!(
+            proportion_cutoff >= 2,
+            "proportion cutoff must be at least 2"
+        );
+
+        HealthChecked {
+            source,
+            repetition_cutoff,
+            proportion_cutoff,
+            started: false,
+            failed: false,
+            last: 0,
+            repetitions: 0,
+            reference: 0,
+            window: 0,
+            matches: 0,
+        }
+    }
+
+    /// Whether a health test has failed since the last reset.
+    #[inline]
+    #[must_use]
+    pub const fn is_failed(&self) -> bool {
+        self.failed
+    }
+
+    /// Clear a failure and start over, including the startup test.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.started = false;
+        self.failed = false;
+        self.repetitions = 0;
+        self.window = 0;
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn source(&self) -> &S {
+        &self.source
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> S {
+        self.source
+    }
+
+    /// Run both tests on a sample, returning whether it passed.
+    #[inline]
+    fn check(&mut self, sample: u8) -> bool {
+        if self.repetitions > 0 && sample == self.last {
+            self.repetitions += 1;
+
+            if self.repetitions >= self.repetition_cutoff {
+                return false;
+            }
+        } else {
+            self.last = sample;
+            self.repetitions = 1;
+        }
+
+        if self.window == 0 {
+            self.reference = sample;
+            self.matches = 1;
+        } else if sample == self.reference {
+            self.matches += 1;
+
+            if self.matches >= self.proportion_cutoff {
+                return false;
+            }
+        }
+
+        self.window = (self.window + 1) % WINDOW;
+
+        true
+    }
+
+    #[inline]
+    fn check_all(&mut self, bytes: &[u8]) -> Result<(), EntropyError> {
+        if bytes.iter().all(|&sample| self.check(sample)) {
+            Ok(())
+        } else {
+            self.failed = true;
+
+            Err(EntropyError::HealthTest)
+        }
+    }
+}
+
+impl<S: EntropySource> EntropySource for HealthChecked<S> {
+    fn try_fill(&mut self, bytes: &mut [u8]) -> Result<(), EntropyError> {
+        if self.failed {
+            return Err(EntropyError::HealthTest);
+        }
+
+        if !self.started {
+            let mut startup = [0; 64];
+
+            for _ in 0..STARTUP_SAMPLES / startup.len() {
+                self.source.try_fill(&mut startup)?;
+                self.check_all(&startup)?;
+            }
+
+            self.started = true;
+        }
+
+        self.source.try_fill(bytes)?;
+        self.check_all(bytes)
+    }
+}
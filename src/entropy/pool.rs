@@ -0,0 +1,94 @@
+use super::{fill_with_i64s, mix64, EntropyError, EntropySource, GOLDEN_GAMMA};
+
+/// How far each word of the pool is rotated before a contribution is mixed
+/// in, so repeated contributions land on different bits.
+const ROTATION: u32 = 23;
+
+/// Pool accumulating entropy from any number of contributions over time,
+/// to be drawn from as an [`EntropySource`] later.
+///
+/// Contributions are XORed into a 256-bit state one word at a time,
+/// rotating and multiplying the word so that every bit of a contribution
+/// affects it, the way operating systems stir their pools. This lets
+/// embedded code stir in whatever it has, such as timer readings, ADC
+/// noise or interrupt timings, while it boots, and seed generators once it
+/// is done.
+///
+/// How much entropy the pool holds is up to the contributions, since it
+/// does not estimate it. Drawing from it never fails, and stirs each
+/// output back in, so successive draws differ even without new
+/// contributions.
+#[derive(Debug, Clone, Default)]
+pub struct EntropyPool {
+    state: [u64; 4],
+    index: usize,
+    draws: u64,
+}
+
+impl EntropyPool {
+    /// Create an empty pool.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> EntropyPool {
+        EntropyPool {
+            state: [0; 4],
+            index: 0,
+            draws: 0,
+        }
+    }
+
+    /// Stir an `i64` into the pool.
+    #[inline]
+    pub fn stir_i64(&mut self, value: i64) {
+        let word = &mut self.state[self.index];
+
+        *word = (word.rotate_left(ROTATION) ^ value as u64).wrapping_mul(GOLDEN_GAMMA);
+        self.index = (self.index + 1) % self.state.len();
+    }
+
+    /// Stir `bytes` into the pool, 8 at a time in little-endian order, with
+    /// the last ones padded with zeros.
+    pub fn stir(&mut self, bytes: &[u8]) {
+        for chunk in bytes.chunks(8) {
+            let mut word = [0; 8];
+
+            word[..chunk.len()].copy_from_slice(chunk);
+            self.stir_i64(i64::from_le_bytes(word));
+        }
+    }
+
+    /// Stir an `i64` from `source` into the pool.
+    #[inline]
+    pub fn stir_from(&mut self, mut source: impl EntropySource) -> Result<(), EntropyError> {
+        self.stir_i64(source.try_next_i64()?);
+
+        Ok(())
+    }
+
+    /// Draw an `i64` from the pool.
+    #[inline]
+    #[must_use]
+    pub fn draw(&mut self) -> i64 {
+        self.draws = self.draws.wrapping_add(GOLDEN_GAMMA);
+
+        let [a, b, c, d] = self.state;
+        let folded = a ^ b.rotate_left(16) ^ c.rotate_left(32) ^ d.rotate_left(48);
+        let output = mix64(folded.wrapping_add(self.draws)) as i64;
+
+        self.stir_i64(output);
+
+        output
+    }
+}
+
+impl EntropySource for EntropyPool {
+    #[inline]
+    fn try_fill(&mut self, bytes: &mut [u8]) -> Result<(), EntropyError> {
+        fill_with_i64s(bytes, || Ok(self.draw()))
+    }
+
+    #[inline]
+    fn try_next_i64(&mut self) -> Result<i64, EntropyError> {
+        Ok(self.draw())
+    }
+}
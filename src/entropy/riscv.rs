@@ -0,0 +1,136 @@
+use core::arch::asm;
+
+use super::{fill_with_i64s, EntropyError, EntropySource};
+
+/// The `OPST` field of the `seed` CSR, in its top two bits.
+const OPST_SHIFT: u32 = 30;
+
+const OPST_BIST: u32 = 0b00;
+const OPST_WAIT: u32 = 0b01;
+const OPST_ES16: u32 = 0b10;
+const OPST_DEAD: u32 = 0b11;
+
+/// How many times to poll the `seed` CSR for a sample before giving up.
+const POLL_LIMIT: u32 = 1 << 16;
+
+/// How many 16-bit samples make up an `i64`, conditioning them 2:1 by
+/// folding as the Zkr specification asks of raw samples.
+const SAMPLES: usize = 8;
+
+/// Read the `seed` CSR.
+///
+/// The CSR must be accessed with a write, or the read is illegal, so this
+/// swaps in zero like `csrrw rd, seed, x0`.
+///
+/// # Safety
+///
+/// The `seed` CSR must exist and be accessible from the current privilege
+/// mode, or this traps.
+#[inline]
+unsafe fn read_seed() -> u32 {
+    let seed: usize;
+
+    asm!("csrrw {}, 0x015, x0", out(reg) seed, options(nomem, nostack));
+
+    seed as u32
+}
+
+/// Poll the `seed` CSR until it reports 16 bits of entropy, or `None` if
+/// the entropy source is dead or never became ready.
+///
+/// # Safety
+///
+/// Same as [`read_seed`].
+#[inline]
+unsafe fn es16() -> Option<u16> {
+    for _ in 0..POLL_LIMIT {
+        let seed = read_seed();
+
+        match seed >> OPST_SHIFT {
+            OPST_ES16 => return Some(seed as u16),
+            // The source is testing itself or gathering entropy.
+            OPST_BIST | OPST_WAIT => continue,
+            OPST_DEAD => return None,
+            _ => unreachable!(),
+        }
+    }
+
+    None
+}
+
+#[inline]
+unsafe fn zkr() -> Option<u64> {
+    let mut raw = [0u64; 2];
+
+    for i in 0..SAMPLES {
+        raw[i / 4] |= (es16()? as u64) << (16 * (i % 4));
+    }
+
+    Some(raw[0] ^ raw[1])
+}
+
+/// Entropy source reading the `seed` CSR of the RISC-V Zkr extension.
+///
+/// Raw 16-bit samples are polled until the source reports them ready, and
+/// folded together two to one, since the specification only promises them
+/// to be full entropy once conditioned.
+///
+/// Whether the CSR can be read from supervisor or user mode is chosen by
+/// machine mode through `mseccfg`, so it can only be detected at compile
+/// time, with the `zkr` target feature.
+#[derive(Debug, Clone, Copy)]
+pub struct Zkr(());
+
+impl Zkr {
+    /// Get the entropy source if the target is compiled with the `zkr`
+    /// feature.
+    #[inline]
+    pub fn new() -> Option<Zkr> {
+        cfg!(target_feature = "zkr").then_some(Zkr(()))
+    }
+
+    /// # Safety
+    ///
+    /// The caller must ensure that the CPU implements Zkr, and that the
+    /// `seed` CSR is accessible from the current privilege mode.
+    #[inline]
+    pub unsafe fn new_unchecked() -> Zkr {
+        Zkr(())
+    }
+
+    /// Read 64 bits of entropy, or `None` if the entropy source is dead or
+    /// never became ready.
+    #[inline]
+    pub fn try_next_u64(self) -> Option<u64> {
+        // SAFETY: If we have a `Zkr`, then the seed CSR must be accessible.
+        unsafe { zkr() }
+    }
+
+    #[inline]
+    pub fn try_next_i64(self) -> Option<i64> {
+        self.try_next_u64().map(|x| x as i64)
+    }
+
+    #[inline]
+    pub fn next_u64(self) -> u64 {
+        self.try_next_u64()
+            .expect("failed to read entropy from the seed CSR")
+    }
+
+    #[inline]
+    pub fn next_i64(self) -> i64 {
+        self.next_u64() as i64
+    }
+}
+
+impl EntropySource for Zkr {
+    #[inline]
+    fn try_fill(&mut self, bytes: &mut [u8]) -> Result<(), EntropyError> {
+        fill_with_i64s(bytes, || EntropySource::try_next_i64(self))
+    }
+
+    #[inline]
+    fn try_next_i64(&mut self) -> Result<i64, EntropyError> {
+        Zkr::try_next_i64(*self).ok_or(EntropyError::Failed)
+    }
+}
@@ -0,0 +1,68 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use super::{EntropyError, EntropyPool, EntropySource};
+
+/// How many bytes of seed the file holds.
+const SEED_LEN: usize = 32;
+
+/// Entropy source keeping a seed in a file across runs, the random seed
+/// file of operating systems.
+///
+/// The file is read when the source is opened, and every read from the
+/// source stirs in an `i64` of fresh entropy from another source, then
+/// writes back a new seed drawn after the output. A device with little
+/// entropy at boot still gets different values from the last boot, as
+/// long as the file was written.
+///
+/// The output and the written seed are drawn separately from an
+/// [`EntropyPool`], so the file is not a copy of the output. It is not
+/// cryptographic either, and the file should be kept private.
+#[derive(Debug, Clone)]
+pub struct SeedFileSource<S> {
+    path: PathBuf,
+    fresh: S,
+    pool: EntropyPool,
+}
+
+impl<S: EntropySource> SeedFileSource<S> {
+    /// Open the seed file at `path`, which is fine if it does not exist yet,
+    /// and mix in `fresh` on every read.
+    pub fn open(path: impl Into<PathBuf>, fresh: S) -> Result<SeedFileSource<S>, EntropyError> {
+        let path = path.into();
+        let mut pool = EntropyPool::new();
+
+        match fs::read(&path) {
+            Ok(seed) => pool.stir(&seed),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {}
+            Err(error) => return Err(error.into()),
+        }
+
+        Ok(SeedFileSource { path, fresh, pool })
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Draw a new seed from the pool and write it to the file.
+    pub fn save(&mut self) -> Result<(), EntropyError> {
+        let mut seed = [0; SEED_LEN];
+
+        self.pool.try_fill(&mut seed)?;
+
+        Ok(fs::write(&self.path, seed)?)
+    }
+}
+
+impl<S: EntropySource> EntropySource for SeedFileSource<S> {
+    fn try_fill(&mut self, bytes: &mut [u8]) -> Result<(), EntropyError> {
+        self.pool.stir_from(&mut self.fresh)?;
+        self.pool.try_fill(bytes)?;
+        self.save()
+    }
+}
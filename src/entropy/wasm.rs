@@ -0,0 +1,61 @@
+use js_sys::Uint8Array;
+use wasm_bindgen::{prelude::wasm_bindgen, JsCast, JsValue};
+
+use super::{EntropyError, EntropySource};
+
+/// The most bytes `getRandomValues` fills in one call.
+const MAX_CHUNK: usize = 65536;
+
+#[wasm_bindgen]
+extern "C" {
+    type Global;
+
+    #[wasm_bindgen(method, getter)]
+    fn crypto(this: &Global) -> JsValue;
+
+    type Crypto;
+
+    #[wasm_bindgen(method, js_name = getRandomValues, catch)]
+    fn get_random_values(this: &Crypto, buf: &Uint8Array) -> Result<(), JsValue>;
+}
+
+/// Entropy source backed by the Web Crypto API's `crypto.getRandomValues`,
+/// as found in browsers, web workers and Node.js.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WebCrypto;
+
+impl WebCrypto {
+    /// Fill `bytes` with `crypto.getRandomValues`, or return the exception
+    /// it threw, or `undefined` if there is no `crypto`.
+    pub fn try_fill_bytes(bytes: &mut [u8]) -> Result<(), JsValue> {
+        let crypto = js_sys::global().unchecked_into::<Global>().crypto();
+
+        if crypto.is_undefined() {
+            return Err(crypto);
+        }
+
+        let crypto = crypto.unchecked_into::<Crypto>();
+
+        for chunk in bytes.chunks_mut(MAX_CHUNK) {
+            let array = Uint8Array::new_with_length(chunk.len() as u32);
+
+            crypto.get_random_values(&array)?;
+            array.copy_to(chunk);
+        }
+
+        Ok(())
+    }
+}
+
+impl EntropySource for WebCrypto {
+    #[inline]
+    fn try_fill(&mut self, bytes: &mut [u8]) -> Result<(), EntropyError> {
+        WebCrypto::try_fill_bytes(bytes).map_err(|error| {
+            if error.is_undefined() {
+                EntropyError::Unsupported
+            } else {
+                EntropyError::Failed
+            }
+        })
+    }
+}
@@ -0,0 +1,60 @@
+use core::{ffi::c_void, fmt, ptr};
+
+use super::{EntropyError, EntropySource};
+
+const BCRYPT_USE_SYSTEM_PREFERRED_RNG: u32 = 0x0000_0002;
+
+#[link(name = "bcrypt")]
+extern "system" {
+    fn BCryptGenRandom(algorithm: *mut c_void, buffer: *mut u8, buffer_len: u32, flags: u32)
+        -> i32;
+}
+
+/// The failing `NTSTATUS` returned by `BCryptGenRandom`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NtStatus(pub i32);
+
+impl fmt::Display for NtStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "BCryptGenRandom failed with NTSTATUS {:#010x}", self.0)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NtStatus {}
+
+/// Entropy source backed by Windows' system-preferred RNG, through
+/// `BCryptGenRandom`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BCrypt;
+
+impl BCrypt {
+    /// Fill `bytes` from the system-preferred RNG.
+    pub fn try_fill_bytes(bytes: &mut [u8]) -> Result<(), NtStatus> {
+        for chunk in bytes.chunks_mut(u32::MAX as usize) {
+            // SAFETY: The buffer is valid for `chunk.len()` bytes, and no
+            // algorithm handle is needed with the system-preferred RNG.
+            let status = unsafe {
+                BCryptGenRandom(
+                    ptr::null_mut(),
+                    chunk.as_mut_ptr(),
+                    chunk.len() as u32,
+                    BCRYPT_USE_SYSTEM_PREFERRED_RNG,
+                )
+            };
+
+            if status < 0 {
+                return Err(NtStatus(status));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl EntropySource for BCrypt {
+    #[inline]
+    fn try_fill(&mut self, bytes: &mut [u8]) -> Result<(), EntropyError> {
+        BCrypt::try_fill_bytes(bytes).map_err(|NtStatus(status)| EntropyError::Os(status))
+    }
+}
@@ -3,7 +3,7 @@ cpufeatures::new!(cpuid_rdrand, "rdrand");
 
 use cfg_if::cfg_if;
 
-use super::EntropySource;
+use super::{fill_with_i64s, EntropyError, EntropySource, NextI64};
 
 cfg_if! {
     if #[cfg(target_arch = "x86_64")] {
@@ -13,11 +13,13 @@ cfg_if! {
     }
 }
 
+/// How many times to try `rdrand` before giving up, as Intel recommends.
 const RDRAND_LIMIT: u32 = 10;
 
 #[target_feature(enable = "rdrand")]
-unsafe fn rdrand_32() -> Option<u32> {
-    for _ in 0..RDRAND_LIMIT {
+#[cfg(target_arch = "x86")]
+unsafe fn rdrand_32(attempts: u32) -> Option<u32> {
+    for _ in 0..attempts {
         let mut rand = 0u32;
 
         if arch::_rdrand32_step(&mut rand) == 1 {
@@ -30,8 +32,8 @@ unsafe fn rdrand_32() -> Option<u32> {
 
 #[target_feature(enable = "rdrand")]
 #[cfg(target_arch = "x86_64")]
-unsafe fn rdrand_64() -> Option<u64> {
-    for _ in 0..RDRAND_LIMIT {
+unsafe fn rdrand_64(attempts: u32) -> Option<u64> {
+    for _ in 0..attempts {
         let mut rand = 0u64;
 
         if arch::_rdrand64_step(&mut rand) == 1 {
@@ -43,35 +45,170 @@ unsafe fn rdrand_64() -> Option<u64> {
 }
 
 #[target_feature(enable = "rdrand")]
-unsafe fn rdrand() -> Option<u64> {
-    if cfg!(not(target_arch = "x86_64")) {
-        let upper = (rdrand_32()? << 32) as u64;
-        let lower = rdrand_32()? as u64;
+unsafe fn rdrand(attempts: u32) -> Option<u64> {
+    cfg_if! {
+        if #[cfg(target_arch = "x86_64")] {
+            rdrand_64(attempts)
+        } else {
+            let upper = (rdrand_32(attempts)? as u64) << 32;
+            let lower = rdrand_32(attempts)? as u64;
 
-        Some(upper | lower)
-    } else {
-        rdrand_64()
+            Some(upper | lower)
+        }
+    }
+}
+
+/// How many times to try `rdseed` before giving up.
+///
+/// Unlike `rdrand`, `rdseed` fails whenever the entropy conditioner has not
+/// produced a fresh seed yet, so Intel recommends retrying it with a pause
+/// in between rather than treating a failure as a fault.
+const RDSEED_LIMIT: u32 = 128;
+
+#[target_feature(enable = "rdseed")]
+#[cfg(target_arch = "x86")]
+unsafe fn rdseed_32(attempts: u32) -> Option<u32> {
+    for _ in 0..attempts {
+        let mut seed = 0u32;
+
+        if arch::_rdseed32_step(&mut seed) == 1 {
+            return Some(seed);
+        }
+
+        arch::_mm_pause();
+    }
+
+    None
+}
+
+#[target_feature(enable = "rdseed")]
+#[cfg(target_arch = "x86_64")]
+unsafe fn rdseed_64(attempts: u32) -> Option<u64> {
+    for _ in 0..attempts {
+        let mut seed = 0u64;
+
+        if arch::_rdseed64_step(&mut seed) == 1 {
+            return Some(seed);
+        }
+
+        arch::_mm_pause();
+    }
+
+    None
+}
+
+#[target_feature(enable = "rdseed")]
+unsafe fn rdseed(attempts: u32) -> Option<u64> {
+    cfg_if! {
+        if #[cfg(target_arch = "x86_64")] {
+            rdseed_64(attempts)
+        } else {
+            let upper = (rdseed_32(attempts)? as u64) << 32;
+            let lower = rdseed_32(attempts)? as u64;
+
+            Some(upper | lower)
+        }
+    }
+}
+
+/// What [`RdRand`] and [`RdSeed`] do once every attempt at a value failed.
+#[derive(Debug, Clone, Copy)]
+pub enum OnFailure {
+    /// Give up, returning `None` or an error.
+    Error,
+    /// Start over with as many attempts again, until a value is ready.
+    Retry,
+    /// Use the value of another source instead.
+    FallBack(NextI64),
+}
+
+/// How [`RdRand`] and [`RdSeed`] retry an instruction that has no value
+/// ready.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// How many times to execute the instruction before it has failed, where
+    /// zero counts as one.
+    pub attempts: u32,
+    pub on_failure: OnFailure,
+}
+
+impl RetryPolicy {
+    /// Retry with `f` until `on_failure` says to stop.
+    #[inline]
+    fn get(self, f: impl Fn(u32) -> Option<u64>) -> Option<u64> {
+        let attempts = self.attempts.max(1);
+
+        loop {
+            if let Some(x) = f(attempts) {
+                break Some(x);
+            }
+
+            match self.on_failure {
+                OnFailure::Error => break None,
+                OnFailure::Retry => continue,
+                OnFailure::FallBack(next_i64) => break Some(next_i64() as u64),
+            }
+        }
     }
 }
 
+/// Fill `bytes` with the little-endian bytes of successive `u64`s from
+/// `next`.
+#[inline]
+fn fill_bytes(bytes: &mut [u8], mut next: impl FnMut() -> Option<u64>) -> Result<(), EntropyError> {
+    fill_with_i64s(bytes, || {
+        next().map(|x| x as i64).ok_or(EntropyError::NotReady)
+    })
+}
+
+/// Entropy source reading the CPU's DRBG with `rdrand`.
 #[derive(Debug, Clone, Copy)]
-pub struct RdRand(());
+pub struct RdRand {
+    policy: RetryPolicy,
+}
 
 impl RdRand {
+    /// The policy of a new `RdRand`: 10 attempts, as Intel recommends,
+    /// then an error.
+    pub const DEFAULT_POLICY: RetryPolicy = RetryPolicy {
+        attempts: RDRAND_LIMIT,
+        on_failure: OnFailure::Error,
+    };
+
     #[inline]
     pub fn new() -> Option<RdRand> {
-        cpuid_rdrand::get().then_some(RdRand(()))
+        // SAFETY: The CPU was just checked to support `rdrand`.
+        cpuid_rdrand::get().then(|| unsafe { RdRand::new_unchecked() })
     }
 
+    /// # Safety
+    ///
+    /// The caller must ensure that the CPU supports the `rdrand` instruction.
     #[inline]
     pub unsafe fn new_unchecked() -> RdRand {
-        RdRand(())
+        RdRand {
+            policy: RdRand::DEFAULT_POLICY,
+        }
+    }
+
+    /// Use `policy` to retry `rdrand`.
+    #[inline]
+    #[must_use]
+    pub const fn with_policy(self, policy: RetryPolicy) -> RdRand {
+        RdRand { policy }
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn policy(&self) -> RetryPolicy {
+        self.policy
     }
 
+    /// Read 64 bits, or `None` if none were ready and the policy gave up.
     #[inline]
     pub fn try_next_u64(self) -> Option<u64> {
         // SAFETY: If we have a `RdRand`, then the rdrand instruction must exist.
-        unsafe { rdrand() }
+        self.policy.get(|attempts| unsafe { rdrand(attempts) })
     }
 
     #[inline]
@@ -89,13 +226,126 @@ impl RdRand {
     pub fn next_i64(self) -> i64 {
         self.next_u64() as i64
     }
+
+    /// Fill `bytes` with `rdrand`, 8 bytes at a time.
+    #[inline]
+    pub fn try_fill_bytes(self, bytes: &mut [u8]) -> Result<(), EntropyError> {
+        fill_bytes(bytes, || self.try_next_u64())
+    }
+
+    #[inline]
+    pub fn fill_bytes(self, bytes: &mut [u8]) {
+        self.try_fill_bytes(bytes)
+            .expect("failed to generate random bytes with rdrand")
+    }
 }
 
 impl EntropySource for RdRand {
-    fn get_entropy(self) -> super::NextI64 {
-        || {
-            // SAFETY: Same invariants discussed inside of [`RdRand::try_next_u64`].
-            unsafe { RdRand::new_unchecked().next_u64() as i64 }
+    #[inline]
+    fn try_fill(&mut self, bytes: &mut [u8]) -> Result<(), EntropyError> {
+        self.try_fill_bytes(bytes)
+    }
+
+    #[inline]
+    fn try_next_i64(&mut self) -> Result<i64, EntropyError> {
+        RdRand::try_next_i64(*self).ok_or(EntropyError::NotReady)
+    }
+}
+
+/// Entropy source reading seeds straight from the CPU's entropy conditioner
+/// with `rdseed`.
+///
+/// Where `rdrand` returns values from a DRBG that is only reseeded now and
+/// then, every `rdseed` value is a fresh seed, which makes it the better
+/// choice for seeding another generator, at the cost of being slower and
+/// failing more often under load.
+#[derive(Debug, Clone, Copy)]
+pub struct RdSeed {
+    policy: RetryPolicy,
+}
+
+impl RdSeed {
+    /// The policy of a new `RdSeed`: 128 attempts with a pause in between,
+    /// then an error.
+    pub const DEFAULT_POLICY: RetryPolicy = RetryPolicy {
+        attempts: RDSEED_LIMIT,
+        on_failure: OnFailure::Error,
+    };
+
+    #[inline]
+    pub fn new() -> Option<RdSeed> {
+        // SAFETY: The CPU was just checked to support `rdseed`.
+        cpuid_rdseed::get().then(|| unsafe { RdSeed::new_unchecked() })
+    }
+
+    /// # Safety
+    ///
+    /// The caller must ensure that the CPU supports the `rdseed` instruction.
+    #[inline]
+    pub unsafe fn new_unchecked() -> RdSeed {
+        RdSeed {
+            policy: RdSeed::DEFAULT_POLICY,
         }
     }
+
+    /// Use `policy` to retry `rdseed`.
+    #[inline]
+    #[must_use]
+    pub const fn with_policy(self, policy: RetryPolicy) -> RdSeed {
+        RdSeed { policy }
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn policy(&self) -> RetryPolicy {
+        self.policy
+    }
+
+    /// Read a seed, retrying with a pause in between while none is ready,
+    /// or `None` if none became ready and the policy gave up.
+    #[inline]
+    pub fn try_next_u64(self) -> Option<u64> {
+        // SAFETY: If we have a `RdSeed`, then the rdseed instruction must exist.
+        self.policy.get(|attempts| unsafe { rdseed(attempts) })
+    }
+
+    #[inline]
+    pub fn try_next_i64(self) -> Option<i64> {
+        self.try_next_u64().map(|x| x as i64)
+    }
+
+    #[inline]
+    pub fn next_u64(self) -> u64 {
+        self.try_next_u64()
+            .expect("failed to generate random number with rdseed")
+    }
+
+    #[inline]
+    pub fn next_i64(self) -> i64 {
+        self.next_u64() as i64
+    }
+
+    /// Fill `bytes` with `rdseed`, 8 bytes at a time.
+    #[inline]
+    pub fn try_fill_bytes(self, bytes: &mut [u8]) -> Result<(), EntropyError> {
+        fill_bytes(bytes, || self.try_next_u64())
+    }
+
+    #[inline]
+    pub fn fill_bytes(self, bytes: &mut [u8]) {
+        self.try_fill_bytes(bytes)
+            .expect("failed to generate random bytes with rdseed")
+    }
+}
+
+impl EntropySource for RdSeed {
+    #[inline]
+    fn try_fill(&mut self, bytes: &mut [u8]) -> Result<(), EntropyError> {
+        self.try_fill_bytes(bytes)
+    }
+
+    #[inline]
+    fn try_next_i64(&mut self) -> Result<i64, EntropyError> {
+        RdSeed::try_next_i64(*self).ok_or(EntropyError::NotReady)
+    }
 }
@@ -0,0 +1,79 @@
+use crate::entropy::{EntropyError, EntropySource};
+
+/// Random number generator reading straight from an [`EntropySource`].
+///
+/// It has the same methods as [`JavaRng`](crate::JavaRng), so hardware or
+/// operating system entropy can be used wherever its API is expected, but
+/// every value is drawn from the source, so its output is neither
+/// reproducible nor what Java would produce.
+#[derive(Debug, Clone, Default)]
+pub struct EntropyRng<S> {
+    source: S,
+    pub(crate) next_gaussian: Option<f64>,
+}
+
+impl<S: EntropySource> EntropyRng<S> {
+    /// Create a random number generator reading from `source`.
+    #[inline]
+    #[must_use]
+    pub const fn new(source: S) -> EntropyRng<S> {
+        EntropyRng {
+            source,
+            next_gaussian: None,
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn source(&self) -> &S {
+        &self.source
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn source_mut(&mut self) -> &mut S {
+        &mut self.source
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> S {
+        self.source
+    }
+}
+
+impl<S: EntropySource> EntropyRng<S> {
+    #[inline]
+    #[must_use]
+    pub(crate) fn next(&mut self, bits: u8) -> i32 {
+        let mut bytes = [0; 4];
+
+        self.next_bytes(&mut bytes);
+
+        (u32::from_le_bytes(bytes) >> (32 - bits)) as i32
+    }
+}
+
+impl_random!([S: EntropySource] EntropyRng<S>);
+
+impl<S: EntropySource> EntropyRng<S> {
+    /// Fill `bytes` from the entropy source.
+    #[inline]
+    pub fn try_next_bytes(&mut self, bytes: &mut [u8]) -> Result<(), EntropyError> {
+        self.source.try_fill(bytes)
+    }
+
+    #[inline]
+    pub fn next_bytes(&mut self, bytes: &mut [u8]) {
+        if let Err(error) = self.try_next_bytes(bytes) {
+            panic!("{error}");
+        }
+    }
+}
+
+impl<S: EntropySource> From<S> for EntropyRng<S> {
+    #[inline]
+    fn from(source: S) -> EntropyRng<S> {
+        EntropyRng::new(source)
+    }
+}
@@ -0,0 +1,88 @@
+//! Gaussians in fixed point, for targets without an FPU, where the soft-float
+//! logarithm and square root of `next_gaussian` dominate its runtime.
+//!
+//! These use the basic Box–Muller transform, with every step done on
+//! integers: the logarithm bit by bit with squarings, the square root with
+//! [`u64::isqrt`], and the rotation by the random angle with CORDIC, which
+//! only shifts and adds. They are not Java's gaussians, which stay the
+//! default everywhere else.
+
+/// How many fractional bits the gaussians here have, as Q16.16 integers.
+pub const FRACTION_BITS: u32 = 16;
+
+/// `2 * ln(2)`, in Q2.30.
+const TWO_LN_2: u64 = 1_488_522_236;
+
+/// The gain CORDIC divides by after [`ATAN`], in Q0.32.
+const CORDIC_GAIN: u64 = 2_608_131_496;
+
+/// `atan(2^-i)` as binary angles, where `2^32` is a full turn.
+const ATAN: [i64; 30] = [
+    536870912, 316933406, 167458907, 85004756, 42667331, 21354465, 10679838, 5340245, 2670163,
+    1335087, 667544, 333772, 166886, 83443, 41722, 20861, 10430, 5215, 2608, 1304, 652, 326, 163,
+    81, 41, 20, 10, 5, 3, 1,
+];
+
+/// `-log2(u / 2^32)` for `u` in `1..=2^32`, in Q8.24.
+#[inline]
+fn neg_log2(u: u64) -> u64 {
+    // u = 2^k * x, with x in [1, 2) as Q1.31.
+    let k = 63 - u.leading_zeros() as u64;
+    let mut x = (u << (63 - k)) >> 32;
+    let mut fraction = 0;
+
+    // Each squaring of x moves the next bit of log2(x) into the integer part.
+    for bit in (0..24).rev() {
+        x = (x * x) >> 31;
+
+        if x >= 1 << 32 {
+            x >>= 1;
+            fraction |= 1 << bit;
+        }
+    }
+
+    (32 << 24) - ((k << 24) | fraction)
+}
+
+/// Generate two independent gaussians with mean 0 and standard deviation 1,
+/// as Q16.16 integers, from two `nextInt()` values.
+///
+/// They are within a couple of units in the last place of the exact
+/// transform. As the uniform halves have 32 bits, their magnitude is at
+/// most `sqrt(64 * ln(2))`, about 6.66, so the tails past that are cut off.
+#[must_use]
+pub fn gaussian_pair(mut next_i32: impl FnMut() -> i32) -> (i32, i32) {
+    // The radius, sqrt(-2 * ln(u1)), with u1 in (0, 1].
+    let u1 = next_i32() as u32 as u64 + 1;
+    let r2 = (neg_log2(u1) * TWO_LN_2) >> 30;
+    let r = (r2 << 24).isqrt();
+
+    // Rotate (r, 0) by the angle with CORDIC, first turning it around by pi
+    // if the angle is outside [-pi / 2, pi / 2], where CORDIC converges.
+    let mut angle = next_i32();
+    let mut x = ((r * CORDIC_GAIN) >> 24) as i64;
+    let mut y = 0i64;
+
+    if !(-(1 << 30)..=1 << 30).contains(&angle) {
+        angle = angle.wrapping_add(i32::MIN);
+        x = -x;
+    }
+
+    let mut z = angle as i64;
+
+    for (i, atan) in ATAN.iter().enumerate() {
+        let (dx, dy) = (y >> i, x >> i);
+
+        if z >= 0 {
+            (x, y, z) = (x - dx, y + dy, z - atan);
+        } else {
+            (x, y, z) = (x + dx, y - dy, z + atan);
+        }
+    }
+
+    // From Q32 to Q16.16, rounded.
+    let shift = 32 - FRACTION_BITS;
+    let round = |v: i64| ((v + (1 << (shift - 1))) >> shift) as i32;
+
+    (round(x), round(y))
+}
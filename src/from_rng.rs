@@ -0,0 +1,102 @@
+use crate::JavaRng;
+
+/// Types that can be generated from a [`JavaRng`], backing
+/// [`JavaRng::random`].
+///
+/// Each value is drawn the way Java code would write it by hand, so a
+/// value generated here can be reproduced in Java with the same calls:
+///
+/// - `bool`, `i32`, `i64`, `f32` and `f64` use `nextBoolean`, `nextInt`,
+///   `nextLong`, `nextFloat` and `nextDouble`.
+/// - Smaller integers truncate a `nextInt()`, like `(byte) nextInt()`.
+/// - `isize` and `usize` truncate a `nextLong()`.
+/// - 128-bit integers use two `nextLong()`s, upper bits first.
+/// - `char` uses `nextInt(0x110000)`, drawing again on surrogates.
+/// - `Option<T>` draws a `nextBoolean()`, then the value if it was `true`.
+/// - Arrays and tuples draw their elements in order.
+pub trait FromJavaRng: Sized {
+    /// Generate a value with `rng`.
+    fn from_rng(rng: &mut JavaRng) -> Self;
+}
+
+macro_rules! impl_from_rng {
+    ($($ty:ty => |$rng:ident| $expr:expr),* $(,)?) => {
+        $(
+            impl FromJavaRng for $ty {
+                #[inline]
+                fn from_rng($rng: &mut JavaRng) -> $ty {
+                    $expr
+                }
+            }
+        )*
+    };
+}
+
+impl_from_rng! {
+    bool => |rng| rng.next_bool(),
+    i8 => |rng| rng.next_i32() as i8,
+    u8 => |rng| rng.next_i32() as u8,
+    i16 => |rng| rng.next_i32() as i16,
+    u16 => |rng| rng.next_i32() as u16,
+    i32 => |rng| rng.next_i32(),
+    u32 => |rng| rng.next_i32() as u32,
+    i64 => |rng| rng.next_i64(),
+    u64 => |rng| rng.next_i64() as u64,
+    isize => |rng| rng.next_i64() as isize,
+    usize => |rng| rng.next_i64() as usize,
+    i128 => |rng| rng.next_i128(),
+    u128 => |rng| rng.next_u128(),
+    f32 => |rng| rng.next_f32(),
+    f64 => |rng| rng.next_f64(),
+}
+
+impl FromJavaRng for char {
+    #[inline]
+    fn from_rng(rng: &mut JavaRng) -> char {
+        loop {
+            if let Some(c) = char::from_u32(rng.next_i32_bounded(0x11_0000) as u32) {
+                break c;
+            }
+        }
+    }
+}
+
+impl<T: FromJavaRng> FromJavaRng for Option<T> {
+    #[inline]
+    fn from_rng(rng: &mut JavaRng) -> Option<T> {
+        rng.next_bool().then(|| T::from_rng(rng))
+    }
+}
+
+impl<T: FromJavaRng, const N: usize> FromJavaRng for [T; N] {
+    #[inline]
+    fn from_rng(rng: &mut JavaRng) -> [T; N] {
+        core::array::from_fn(|_| T::from_rng(rng))
+    }
+}
+
+macro_rules! impl_from_rng_tuple {
+    ($($name:ident)*) => {
+        impl<$($name: FromJavaRng),*> FromJavaRng for ($($name,)*) {
+            #[inline]
+            #[allow(clippy::unused_unit)]
+            fn from_rng(_rng: &mut JavaRng) -> ($($name,)*) {
+                ($($name::from_rng(_rng),)*)
+            }
+        }
+    };
+}
+
+impl_from_rng_tuple!();
+impl_from_rng_tuple!(A);
+impl_from_rng_tuple!(A B);
+impl_from_rng_tuple!(A B C);
+impl_from_rng_tuple!(A B C D);
+impl_from_rng_tuple!(A B C D E);
+impl_from_rng_tuple!(A B C D E F);
+impl_from_rng_tuple!(A B C D E F G);
+impl_from_rng_tuple!(A B C D E F G H);
+impl_from_rng_tuple!(A B C D E F G H I);
+impl_from_rng_tuple!(A B C D E F G H I J);
+impl_from_rng_tuple!(A B C D E F G H I J K);
+impl_from_rng_tuple!(A B C D E F G H I J K L);
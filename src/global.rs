@@ -0,0 +1,41 @@
+//! One [`JavaRng`] shared by the whole program, guarded by a
+//! [`critical_section`], for `no_std` targets without threads.
+//!
+//! The generator starts from a seed of zero, like [`JavaRng::new_zeroed`],
+//! so it is deterministic until reseeded with [`seed`]. Interrupt handlers
+//! and the main program can both draw from it, continuing one sequence.
+//!
+//! An implementation of `critical-section` must be provided, usually by the
+//! target's HAL or runtime crate.
+
+use core::cell::RefCell;
+
+use critical_section::Mutex;
+
+use crate::JavaRng;
+
+static RNG: Mutex<RefCell<JavaRng>> = Mutex::new(RefCell::new(JavaRng::new_zeroed()));
+
+/// Call `f` with the global random number generator, inside a critical
+/// section.
+///
+/// # Panics
+///
+/// Panics if called again from inside `f`.
+#[inline]
+pub fn with<R>(f: impl FnOnce(&mut JavaRng) -> R) -> R {
+    critical_section::with(|cs| f(&mut RNG.borrow_ref_mut(cs)))
+}
+
+/// Reset the global random number generator to the specified `seed`, like
+/// [`JavaRng::set_seed`].
+#[inline]
+pub fn seed(seed: i64) {
+    with(|rng| rng.set_seed(seed))
+}
+
+/// Replace the global random number generator, returning the old one.
+#[inline]
+pub fn replace(rng: JavaRng) -> JavaRng {
+    with(|old| core::mem::replace(old, rng))
+}
@@ -0,0 +1,357 @@
+use core::ops::Range;
+
+/// Random number generator that replicates the behavior of Kotlin's
+/// `kotlin.random.Random(seed)`, an XorWow generator.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct KotlinRng {
+    x: i32,
+    y: i32,
+    z: i32,
+    w: i32,
+    v: i32,
+    addend: i32,
+}
+
+impl KotlinRng {
+    /// Create a random number generator with the specified `Long` seed, like
+    /// `Random(seed: Long)`.
+    #[inline]
+    #[must_use]
+    pub fn with_seed(seed: i64) -> KotlinRng {
+        KotlinRng::from_seeds(seed as i32, (seed >> 32) as i32)
+    }
+
+    /// Create a random number generator with the specified `Int` seed, like
+    /// `Random(seed: Int)`.
+    #[inline]
+    #[must_use]
+    pub fn with_seed_i32(seed: i32) -> KotlinRng {
+        KotlinRng::from_seeds(seed, seed >> 31)
+    }
+
+    #[inline]
+    fn from_seeds(seed1: i32, seed2: i32) -> KotlinRng {
+        let mut rng = KotlinRng {
+            x: seed1,
+            y: seed2,
+            z: 0,
+            w: 0,
+            v: !seed1,
+            addend: (seed1 << 10) ^ (seed2 as u32 >> 4) as i32,
+        };
+
+        // Some trivial seeds produce several values with zeroes in the upper
+        // bits, so Kotlin discards the first 64.
+        for _ in 0..64 {
+            let _ = rng.next_i32();
+        }
+
+        rng
+    }
+}
+
+impl KotlinRng {
+    /// Generate the next `Int`, like `nextInt()`.
+    #[inline]
+    #[must_use]
+    pub fn next_i32(&mut self) -> i32 {
+        let mut t = self.x;
+        t ^= (t as u32 >> 2) as i32;
+
+        self.x = self.y;
+        self.y = self.z;
+        self.z = self.w;
+
+        let v0 = self.v;
+        self.w = v0;
+
+        t = (t ^ (t << 1)) ^ v0 ^ (v0 << 4);
+        self.v = t;
+
+        self.addend = self.addend.wrapping_add(362437);
+
+        t.wrapping_add(self.addend)
+    }
+
+    /// Generate the upper `bits` bits of [`KotlinRng::next_i32`], or zero if
+    /// `bits` is zero.
+    #[inline]
+    #[must_use]
+    pub fn next_bits(&mut self, bits: u8) -> i32 {
+        assert!(bits <= 32, "bits must be at most 32");
+
+        let next = self.next_i32() as u32;
+
+        match bits {
+            0 => 0,
+            bits => (next >> (32 - bits)) as i32,
+        }
+    }
+
+    /// Generate an `i32` in `[0, until)`, like `nextInt(until)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `until` is not positive.
+    #[inline]
+    #[must_use]
+    pub fn next_i32_bounded(&mut self, until: i32) -> i32 {
+        assert!(until > 0, "bound must be positive");
+
+        self.next_i32_ranged(0..until)
+    }
+
+    /// Generate an `i32` in `range`, like `nextInt(from, until)`.
+    ///
+    /// Ranges wider than `2^31` draw whole [`KotlinRng::next_i32`]s until one
+    /// is in range, as Kotlin does.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is empty.
+    #[inline]
+    #[must_use]
+    pub fn next_i32_ranged(&mut self, range: Range<i32>) -> i32 {
+        let Range {
+            start: from,
+            end: until,
+        } = range;
+
+        assert!(from < until, "bound must be greater than origin");
+
+        let n = until.wrapping_sub(from);
+
+        if n > 0 || n == i32::MIN {
+            let rnd = if n & n.wrapping_neg() == n {
+                self.next_bits(fast_log2(n as u32 as u64))
+            } else {
+                loop {
+                    let bits = (self.next_i32() as u32 >> 1) as i32;
+                    let rem = bits % n;
+
+                    if bits.wrapping_sub(rem).wrapping_add(n - 1) >= 0 {
+                        break rem;
+                    }
+                }
+            };
+
+            from.wrapping_add(rnd)
+        } else {
+            loop {
+                let rnd = self.next_i32();
+
+                if (from..until).contains(&rnd) {
+                    break rnd;
+                }
+            }
+        }
+    }
+
+    /// Generate the bits of [`KotlinRng::next_i32`] as a `u32`.
+    #[inline]
+    #[must_use]
+    pub fn next_u32(&mut self) -> u32 {
+        self.next_i32() as u32
+    }
+
+    /// Generate the next `Long`, like `nextLong()`, from two `Int`s, upper
+    /// bits first.
+    #[inline]
+    #[must_use]
+    pub fn next_i64(&mut self) -> i64 {
+        ((self.next_i32() as i64) << 32).wrapping_add(self.next_i32() as i64)
+    }
+
+    /// Generate an `i64` in `[0, until)`, like `nextLong(until)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `until` is not positive.
+    #[inline]
+    #[must_use]
+    pub fn next_i64_bounded(&mut self, until: i64) -> i64 {
+        assert!(until > 0, "bound must be positive");
+
+        self.next_i64_ranged(0..until)
+    }
+
+    /// Generate an `i64` in `range`, like `nextLong(from, until)`.
+    ///
+    /// Ranges wider than `i64::MAX` draw whole [`KotlinRng::next_i64`]s until
+    /// one is in range, as Kotlin does.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is empty.
+    #[inline]
+    #[must_use]
+    pub fn next_i64_ranged(&mut self, range: Range<i64>) -> i64 {
+        let Range {
+            start: from,
+            end: until,
+        } = range;
+
+        assert!(from < until, "bound must be greater than origin");
+
+        let n = until.wrapping_sub(from);
+
+        if n > 0 {
+            let rnd = if n & n.wrapping_neg() == n {
+                let low = n as u32;
+                let high = (n as u64 >> 32) as u32;
+
+                if low != 0 {
+                    self.next_bits(fast_log2(low as u64)) as u32 as i64
+                } else if high == 1 {
+                    self.next_i32() as u32 as i64
+                } else {
+                    let high = (self.next_bits(fast_log2(high as u64)) as i64) << 32;
+
+                    high.wrapping_add(self.next_i32() as u32 as i64)
+                }
+            } else {
+                loop {
+                    let bits = (self.next_i64() as u64 >> 1) as i64;
+                    let rem = bits % n;
+
+                    if bits.wrapping_sub(rem).wrapping_add(n - 1) >= 0 {
+                        break rem;
+                    }
+                }
+            };
+
+            from.wrapping_add(rnd)
+        } else {
+            loop {
+                let rnd = self.next_i64();
+
+                if (from..until).contains(&rnd) {
+                    break rnd;
+                }
+            }
+        }
+    }
+
+    /// Generate the bits of [`KotlinRng::next_i64`] as a `u64`.
+    #[inline]
+    #[must_use]
+    pub fn next_u64(&mut self) -> u64 {
+        self.next_i64() as u64
+    }
+
+    /// Generate a `bool` from one random bit, like `nextBoolean()`.
+    #[inline]
+    #[must_use]
+    pub fn next_bool(&mut self) -> bool {
+        self.next_bits(1) != 0
+    }
+
+    /// Generate an `f32` in `[0, 1)` from 24 random bits, like `nextFloat()`.
+    #[inline]
+    #[must_use]
+    pub fn next_f32(&mut self) -> f32 {
+        self.next_bits(24) as f32 / (1 << 24) as f32
+    }
+
+    /// Generate an `f64` in `[0, 1)` from 53 random bits, like `nextDouble()`.
+    #[inline]
+    #[must_use]
+    pub fn next_f64(&mut self) -> f64 {
+        let high = (self.next_bits(26) as i64) << 27;
+        let low = self.next_bits(27) as i64;
+
+        (high + low) as f64 / (1_i64 << 53) as f64
+    }
+
+    /// Generate an `f64` in `[0, until)`, like `nextDouble(until)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `until` is not positive, or is NaN.
+    #[inline]
+    #[must_use]
+    pub fn next_f64_bounded(&mut self, until: f64) -> f64 {
+        self.next_f64_ranged(0.0..until)
+    }
+
+    /// Generate an `f64` in `range`, like `nextDouble(from, until)`.
+    ///
+    /// Ranges too wide for their size to be finite are scaled by halves, as
+    /// Kotlin does.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is empty, or either end is NaN.
+    #[inline]
+    #[must_use]
+    pub fn next_f64_ranged(&mut self, range: Range<f64>) -> f64 {
+        let Range {
+            start: from,
+            end: until,
+        } = range;
+
+        assert!(from < until, "bound must be greater than origin");
+
+        let size = until - from;
+
+        let r = if size.is_infinite() && from.is_finite() && until.is_finite() {
+            let r1 = self.next_f64() * (until / 2.0 - from / 2.0);
+
+            from + r1 + r1
+        } else {
+            from + self.next_f64() * size
+        };
+
+        if r >= until {
+            next_down(until)
+        } else {
+            r
+        }
+    }
+
+    /// Fill `bytes` with the little-endian bytes of successive
+    /// [`KotlinRng::next_i32`] calls.
+    ///
+    /// Like Kotlin, the trailing bytes come from the upper bits of one more
+    /// value, which is generated even if there are no trailing bytes.
+    #[inline]
+    pub fn next_bytes(&mut self, bytes: &mut [u8]) {
+        let mut chunks = bytes.chunks_exact_mut(4);
+
+        chunks
+            .by_ref()
+            .for_each(|chunk| chunk.copy_from_slice(&self.next_i32().to_le_bytes()));
+
+        let remainder = chunks.into_remainder();
+        let next = self.next_bits(remainder.len() as u8 * 8);
+
+        remainder.copy_from_slice(&next.to_le_bytes()[..remainder.len()]);
+    }
+
+    /// Like [`KotlinRng::next_bytes`], for `i8`s.
+    #[inline]
+    pub fn next_bytes_signed(&mut self, bytes: &mut [i8]) {
+        self.next_bytes(bytemuck::cast_slice_mut(bytes))
+    }
+}
+
+/// The base two logarithm of a power of two.
+#[inline]
+const fn fast_log2(value: u64) -> u8 {
+    63 - value.leading_zeros() as u8
+}
+
+/// The largest `f64` less than `x`.
+#[inline]
+fn next_down(x: f64) -> f64 {
+    if x.is_nan() || x == f64::NEG_INFINITY {
+        x
+    } else if x == 0.0 {
+        -f64::from_bits(1)
+    } else if x > 0.0 {
+        f64::from_bits(x.to_bits() - 1)
+    } else {
+        f64::from_bits(x.to_bits() + 1)
+    }
+}
@@ -3,8 +3,93 @@
 #[cfg(feature = "std")]
 extern crate std;
 
+#[macro_use]
+mod macros;
+
+mod atomic;
+pub use atomic::*;
+
+mod buffered;
+pub use buffered::*;
+
+pub mod commons;
+
+#[cfg(feature = "drbg")]
+pub mod drbg;
 pub mod entropy;
 
+mod entropy_rng;
+pub use entropy_rng::*;
+
+pub mod fixed;
+
+#[cfg(feature = "critical-section")]
+pub mod global;
+
+mod from_rng;
+pub use from_rng::*;
+
+/// Derive [`FromJavaRng`] for a struct or enum, generating its fields in
+/// declaration order.
+#[cfg(feature = "derive")]
+pub use jrand_derive::FromJavaRng;
+
+#[cfg(feature = "mc")]
+pub mod mc;
+
+mod kotlin;
+pub use kotlin::*;
+
 mod math;
+
+#[cfg(feature = "rayon")]
+mod par;
+#[cfg(feature = "rayon")]
+pub use par::*;
+
+mod policy;
+pub use policy::*;
+
+#[cfg(feature = "num-bigint")]
+mod prime;
+
+#[cfg(feature = "rand_core")]
+mod rand06;
+#[cfg(feature = "rand")]
+mod rand08;
+#[cfg(feature = "rand")]
+pub use rand08::*;
+#[cfg(feature = "rand_core_0_9")]
+mod rand09;
+
 mod random;
 pub use random::*;
+
+pub mod split;
+
+pub mod strictmath;
+
+#[cfg(feature = "std")]
+mod thread;
+#[cfg(feature = "std")]
+pub use thread::*;
+
+mod uniform;
+pub use uniform::*;
+
+pub mod ziggurat;
+
+#[cfg(feature = "getrandom")]
+mod native;
+#[cfg(feature = "getrandom")]
+pub use native::*;
+
+#[cfg(any(feature = "sha1prng", feature = "drbg", feature = "getrandom"))]
+mod secure;
+#[cfg(any(feature = "sha1prng", feature = "drbg", feature = "getrandom"))]
+pub use secure::*;
+
+#[cfg(feature = "sha1prng")]
+mod sha1prng;
+#[cfg(feature = "sha1prng")]
+pub use sha1prng::*;
@@ -0,0 +1,1282 @@
+/// Implement the methods `java.util.Random` derives from `next(bits)`.
+///
+/// The type must provide `fn next(&mut self, bits: u8) -> i32` and
+/// `fn next_bytes(&mut self, bytes: &mut [u8])`, and have a
+/// `next_gaussian: Option<f64>` field caching the second gaussian.
+///
+/// Generic types list their generic parameters in brackets first, as in
+/// `impl_random!([const N: usize] Type<N>)`.
+macro_rules! impl_random {
+    ([$($generics:tt)*] $ty:ty) => {
+        const _: () = {
+            use ::core::{
+                iter::{repeat_with, FusedIterator},
+                ops::{Bound, Range, RangeBounds},
+            };
+            use $crate::{consts, math};
+
+            impl<$($generics)*> $ty {
+                #[inline]
+                pub fn next_bytes_signed(&mut self, bytes: &mut [i8]) {
+                    self.next_bytes(bytemuck::cast_slice_mut(bytes))
+                }
+
+                #[inline]
+                #[must_use]
+                pub fn next_i32(&mut self) -> i32 {
+                    self.next(32)
+                }
+
+                #[inline]
+                pub fn fill_i32(&mut self, dest: &mut [i32]) {
+                    dest.iter_mut().for_each(|x| *x = self.next_i32());
+                }
+
+                #[inline]
+                #[must_use]
+                pub fn next_i32_bounded(&mut self, bound: i32) -> i32 {
+                    assert!(bound > 0, "bound must be positive");
+
+                    let max = bound - 1;
+
+                    if bound & max == 0 {
+                        return ((self.next(31) as i64).wrapping_mul(bound as i64) >> 31) as i32;
+                    }
+
+                    loop {
+                        let bits = self.next(31);
+                        let rem = bits % bound;
+
+                        if bits.wrapping_sub(rem).wrapping_add(max) >= 0 {
+                            break rem;
+                        }
+                    }
+                }
+
+                #[inline]
+                pub fn try_next_i32_bounded(
+                    &mut self,
+                    bound: i32,
+                ) -> Result<i32, $crate::BoundError> {
+                    if bound > 0 {
+                        Ok(self.next_i32_bounded(bound))
+                    } else {
+                        Err($crate::BoundError::NotPositive)
+                    }
+                }
+
+                #[inline]
+                #[must_use]
+                pub fn next_i32_ranged(&mut self, range: Range<i32>) -> i32 {
+                    let Range {
+                        start: origin,
+                        end: bound,
+                    } = range;
+
+                    let len = bound.wrapping_sub(origin);
+
+                    if origin >= bound {
+                        self.next_i32()
+                    } else if len > 0 {
+                        self.next_i32_bounded(len) + origin
+                    } else {
+                        loop {
+                            let r = self.next_i32();
+
+                            if (origin..bound).contains(&r) {
+                                break r;
+                            }
+                        }
+                    }
+                }
+
+                #[inline]
+                pub fn try_next_i32_ranged(
+                    &mut self,
+                    range: Range<i32>,
+                ) -> Result<i32, $crate::BoundError> {
+                    if range.start < range.end {
+                        Ok(self.next_i32_ranged(range))
+                    } else {
+                        Err($crate::BoundError::InvalidRange)
+                    }
+                }
+
+                /// Like [`Self::next_i32_ranged`], for any kind of range.
+                ///
+                /// Ranges ending at `i32::MAX` inclusive, which Java cannot
+                /// express, reject values of [`Self::next_i32`] below the
+                /// start.
+                ///
+                /// # Panics
+                ///
+                /// Panics if `range` is empty.
+                #[inline]
+                #[must_use]
+                pub fn next_i32_in(&mut self, range: impl RangeBounds<i32>) -> i32 {
+                    let origin = match range.start_bound() {
+                        Bound::Included(&start) => start as i64,
+                        Bound::Excluded(&start) => start as i64 + 1,
+                        Bound::Unbounded => i32::MIN as i64,
+                    };
+
+                    let bound = match range.end_bound() {
+                        Bound::Included(&end) => end as i64 + 1,
+                        Bound::Excluded(&end) => end as i64,
+                        Bound::Unbounded => i32::MAX as i64 + 1,
+                    };
+
+                    assert!(origin < bound, "bound must be greater than origin");
+
+                    if bound <= i32::MAX as i64 {
+                        self.next_i32_ranged(origin as i32..bound as i32)
+                    } else {
+                        loop {
+                            let r = self.next_i32();
+
+                            if r as i64 >= origin {
+                                break r;
+                            }
+                        }
+                    }
+                }
+
+                #[inline]
+                pub fn i32_iter(&mut self) -> impl FusedIterator<Item = i32> + '_ {
+                    repeat_with(|| self.next_i32())
+                }
+
+                #[inline]
+                pub fn into_i32_iter(mut self) -> impl FusedIterator<Item = i32> {
+                    repeat_with(move || self.next_i32())
+                }
+
+                #[inline]
+                pub fn i32_iter_bounded(
+                    &mut self,
+                    bound: i32,
+                ) -> impl FusedIterator<Item = i32> + '_ {
+                    repeat_with(move || self.next_i32_bounded(bound))
+                }
+
+                #[inline]
+                pub fn into_i32_iter_bounded(
+                    mut self,
+                    bound: i32,
+                ) -> impl FusedIterator<Item = i32> {
+                    repeat_with(move || self.next_i32_bounded(bound))
+                }
+
+                #[inline]
+                pub fn i32_iter_ranged(
+                    &mut self,
+                    range: Range<i32>,
+                ) -> impl FusedIterator<Item = i32> + '_ {
+                    repeat_with(move || self.next_i32_ranged(range.clone()))
+                }
+
+                #[inline]
+                pub fn into_i32_iter_ranged(
+                    mut self,
+                    range: Range<i32>,
+                ) -> impl FusedIterator<Item = i32> {
+                    repeat_with(move || self.next_i32_ranged(range.clone()))
+                }
+
+                #[inline]
+                pub fn i32_stream(
+                    &mut self,
+                    len: usize,
+                ) -> impl ExactSizeIterator<Item = i32> + FusedIterator + '_ {
+                    (0..len).map(move |_| self.next_i32())
+                }
+
+                #[inline]
+                pub fn i32_stream_bounded(
+                    &mut self,
+                    len: usize,
+                    bound: i32,
+                ) -> impl ExactSizeIterator<Item = i32> + FusedIterator + '_ {
+                    (0..len).map(move |_| self.next_i32_bounded(bound))
+                }
+
+                #[inline]
+                pub fn i32_stream_ranged(
+                    &mut self,
+                    len: usize,
+                    range: Range<i32>,
+                ) -> impl ExactSizeIterator<Item = i32> + FusedIterator + '_ {
+                    (0..len).map(move |_| self.next_i32_ranged(range.clone()))
+                }
+
+                #[inline]
+                #[must_use]
+                pub fn next_u32(&mut self) -> u32 {
+                    self.next_i32() as u32
+                }
+
+                #[inline]
+                pub fn u32_iter(&mut self) -> impl FusedIterator<Item = u32> + '_ {
+                    repeat_with(|| self.next_u32())
+                }
+
+                #[inline]
+                pub fn into_u32_iter(mut self) -> impl FusedIterator<Item = u32> {
+                    repeat_with(move || self.next_u32())
+                }
+
+                #[inline]
+                #[must_use]
+                pub fn next_i64(&mut self) -> i64 {
+                    let upper = (self.next_i32() as i64) << 32;
+                    let lower = self.next_i32() as i64;
+
+                    upper.wrapping_add(lower)
+                }
+
+                #[inline]
+                pub fn fill_i64(&mut self, dest: &mut [i64]) {
+                    dest.iter_mut().for_each(|x| *x = self.next_i64());
+                }
+
+                #[inline]
+                #[must_use]
+                pub fn next_i64_bounded(&mut self, bound: i64) -> i64 {
+                    assert!(bound > 0, "bound must be positive");
+
+                    let max = bound - 1;
+                    let bits = self.next_i64();
+
+                    if bound & max == 0 {
+                        return bits & max;
+                    }
+
+                    let mut bits = (bits as u64 >> 1) as i64;
+
+                    loop {
+                        let rem = bits % bound;
+
+                        if bits.wrapping_add(max).wrapping_sub(rem) >= 0 {
+                            break rem;
+                        }
+
+                        bits = (self.next_u64() >> 1) as i64;
+                    }
+                }
+
+                #[inline]
+                pub fn try_next_i64_bounded(
+                    &mut self,
+                    bound: i64,
+                ) -> Result<i64, $crate::BoundError> {
+                    if bound > 0 {
+                        Ok(self.next_i64_bounded(bound))
+                    } else {
+                        Err($crate::BoundError::NotPositive)
+                    }
+                }
+
+                #[inline]
+                #[must_use]
+                pub fn next_i64_ranged(&mut self, range: Range<i64>) -> i64 {
+                    let Range {
+                        start: origin,
+                        end: bound,
+                    } = range;
+
+                    let len = bound.wrapping_sub(origin);
+                    let max = len.wrapping_sub(1);
+
+                    if origin >= bound {
+                        self.next_i64()
+                    } else if len & max == 0 {
+                        (self.next_i64() & max).wrapping_add(origin)
+                    } else if len > 0 {
+                        loop {
+                            let bits = (self.next_u64() >> 1) as i64;
+                            let rem = bits % len;
+
+                            if bits.wrapping_add(max).wrapping_sub(rem) >= 0 {
+                                break rem + origin;
+                            }
+                        }
+                    } else {
+                        loop {
+                            let r = self.next_i64();
+
+                            if (origin..bound).contains(&r) {
+                                break r;
+                            }
+                        }
+                    }
+                }
+
+                #[inline]
+                pub fn try_next_i64_ranged(
+                    &mut self,
+                    range: Range<i64>,
+                ) -> Result<i64, $crate::BoundError> {
+                    if range.start < range.end {
+                        Ok(self.next_i64_ranged(range))
+                    } else {
+                        Err($crate::BoundError::InvalidRange)
+                    }
+                }
+
+                /// Like [`Self::next_i64_ranged`], for any kind of range.
+                ///
+                /// Ranges ending at `i64::MAX` inclusive, which Java cannot
+                /// express, reject values of [`Self::next_i64`] below the
+                /// start.
+                ///
+                /// # Panics
+                ///
+                /// Panics if `range` is empty.
+                #[inline]
+                #[must_use]
+                pub fn next_i64_in(&mut self, range: impl RangeBounds<i64>) -> i64 {
+                    let origin = match range.start_bound() {
+                        Bound::Included(&start) => start as i128,
+                        Bound::Excluded(&start) => start as i128 + 1,
+                        Bound::Unbounded => i64::MIN as i128,
+                    };
+
+                    let bound = match range.end_bound() {
+                        Bound::Included(&end) => end as i128 + 1,
+                        Bound::Excluded(&end) => end as i128,
+                        Bound::Unbounded => i64::MAX as i128 + 1,
+                    };
+
+                    assert!(origin < bound, "bound must be greater than origin");
+
+                    if bound <= i64::MAX as i128 {
+                        self.next_i64_ranged(origin as i64..bound as i64)
+                    } else {
+                        loop {
+                            let r = self.next_i64();
+
+                            if r as i128 >= origin {
+                                break r;
+                            }
+                        }
+                    }
+                }
+
+                #[inline]
+                pub fn i64_iter(&mut self) -> impl FusedIterator<Item = i64> + '_ {
+                    repeat_with(|| self.next_i64())
+                }
+
+                #[inline]
+                pub fn into_i64_iter(mut self) -> impl FusedIterator<Item = i64> {
+                    repeat_with(move || self.next_i64())
+                }
+
+                #[inline]
+                pub fn i64_iter_bounded(
+                    &mut self,
+                    bound: i64,
+                ) -> impl FusedIterator<Item = i64> + '_ {
+                    repeat_with(move || self.next_i64_bounded(bound))
+                }
+
+                #[inline]
+                pub fn into_i64_iter_bounded(
+                    mut self,
+                    bound: i64,
+                ) -> impl FusedIterator<Item = i64> {
+                    repeat_with(move || self.next_i64_bounded(bound))
+                }
+
+                #[inline]
+                pub fn i64_iter_ranged(
+                    &mut self,
+                    range: Range<i64>,
+                ) -> impl FusedIterator<Item = i64> + '_ {
+                    repeat_with(move || self.next_i64_ranged(range.clone()))
+                }
+
+                #[inline]
+                pub fn into_i64_iter_ranged(
+                    mut self,
+                    range: Range<i64>,
+                ) -> impl FusedIterator<Item = i64> {
+                    repeat_with(move || self.next_i64_ranged(range.clone()))
+                }
+
+                #[inline]
+                pub fn i64_stream(
+                    &mut self,
+                    len: usize,
+                ) -> impl ExactSizeIterator<Item = i64> + FusedIterator + '_ {
+                    (0..len).map(move |_| self.next_i64())
+                }
+
+                #[inline]
+                pub fn i64_stream_bounded(
+                    &mut self,
+                    len: usize,
+                    bound: i64,
+                ) -> impl ExactSizeIterator<Item = i64> + FusedIterator + '_ {
+                    (0..len).map(move |_| self.next_i64_bounded(bound))
+                }
+
+                #[inline]
+                pub fn i64_stream_ranged(
+                    &mut self,
+                    len: usize,
+                    range: Range<i64>,
+                ) -> impl ExactSizeIterator<Item = i64> + FusedIterator + '_ {
+                    (0..len).map(move |_| self.next_i64_ranged(range.clone()))
+                }
+
+                #[inline]
+                #[must_use]
+                pub fn next_u64(&mut self) -> u64 {
+                    self.next_i64() as u64
+                }
+
+                #[inline]
+                pub fn u64_iter(&mut self) -> impl FusedIterator<Item = u64> + '_ {
+                    repeat_with(|| self.next_u64())
+                }
+
+                /// Generate an `i128` from two [`Self::next_i64`] calls, the
+                /// first giving the upper 64 bits.
+                #[inline]
+                #[must_use]
+                pub fn next_i128(&mut self) -> i128 {
+                    let upper = (self.next_i64() as i128) << 64;
+                    let lower = self.next_u64() as i128;
+
+                    upper | lower
+                }
+
+                /// Like [`Self::next_i64_ranged`], widened to 128 bits.
+                #[inline]
+                #[must_use]
+                pub fn next_i128_ranged(&mut self, range: Range<i128>) -> i128 {
+                    let Range {
+                        start: origin,
+                        end: bound,
+                    } = range;
+
+                    let len = bound.wrapping_sub(origin);
+                    let max = len.wrapping_sub(1);
+
+                    if origin >= bound {
+                        self.next_i128()
+                    } else if len & max == 0 {
+                        (self.next_i128() & max).wrapping_add(origin)
+                    } else if len > 0 {
+                        loop {
+                            let bits = (self.next_u128() >> 1) as i128;
+                            let rem = bits % len;
+
+                            if bits.wrapping_add(max).wrapping_sub(rem) >= 0 {
+                                break rem + origin;
+                            }
+                        }
+                    } else {
+                        loop {
+                            let r = self.next_i128();
+
+                            if (origin..bound).contains(&r) {
+                                break r;
+                            }
+                        }
+                    }
+                }
+
+                /// Generate a `u128` like [`Self::next_i128`].
+                #[inline]
+                #[must_use]
+                pub fn next_u128(&mut self) -> u128 {
+                    self.next_i128() as u128
+                }
+
+                /// Like [`Self::next_i128_ranged`], with the range shifted
+                /// into `i128`.
+                #[inline]
+                #[must_use]
+                pub fn next_u128_ranged(&mut self, range: Range<u128>) -> u128 {
+                    const SIGN: u128 = 1 << 127;
+
+                    let range = (range.start ^ SIGN) as i128..(range.end ^ SIGN) as i128;
+
+                    self.next_i128_ranged(range) as u128 ^ SIGN
+                }
+
+                #[inline]
+                pub fn into_u64_iter(mut self) -> impl FusedIterator<Item = u64> {
+                    repeat_with(move || self.next_u64())
+                }
+
+                #[inline]
+                #[must_use]
+                pub fn next_bool(&mut self) -> bool {
+                    self.next(1) != 0
+                }
+
+                /// Return `true` with probability `p`, like the Java idiom
+                /// `nextDouble() < p`.
+                ///
+                /// A `p` of at most `0` is never `true`, and one of at least `1`
+                /// always is, but either way a double is drawn.
+                #[inline]
+                #[must_use]
+                pub fn next_bool_with(&mut self, p: f64) -> bool {
+                    self.next_f64() < p
+                }
+
+                /// Return `true` with probability `numerator / denominator`,
+                /// like the Java idiom `nextInt(denominator) < numerator`.
+                ///
+                /// # Panics
+                ///
+                /// Panics if `denominator` is not positive, or if `numerator` is
+                /// not in `0..=denominator`.
+                #[inline]
+                #[must_use]
+                pub fn next_ratio(&mut self, numerator: i32, denominator: i32) -> bool {
+                    assert!(
+                        0 <= numerator && numerator <= denominator,
+                        "numerator must be in 0..=denominator"
+                    );
+
+                    self.next_i32_bounded(denominator) < numerator
+                }
+
+                #[inline]
+                pub fn fill_bool(&mut self, dest: &mut [bool]) {
+                    dest.iter_mut().for_each(|x| *x = self.next_bool());
+                }
+
+                #[inline]
+                pub fn bool_iter(&mut self) -> impl FusedIterator<Item = bool> + '_ {
+                    repeat_with(|| self.next_bool())
+                }
+
+                #[inline]
+                pub fn into_bool_iter(mut self) -> impl FusedIterator<Item = bool> {
+                    repeat_with(move || self.next_bool())
+                }
+
+                #[inline]
+                #[must_use]
+                pub fn next_f32(&mut self) -> f32 {
+                    (self.next(24) as f32) * consts::FLOAT_UNIT
+                }
+
+                #[inline]
+                pub fn fill_f32(&mut self, dest: &mut [f32]) {
+                    dest.iter_mut().for_each(|x| *x = self.next_f32());
+                }
+
+                #[inline]
+                pub fn f32_iter(&mut self) -> impl FusedIterator<Item = f32> + '_ {
+                    repeat_with(|| self.next_f32())
+                }
+
+                #[inline]
+                pub fn into_f32_iter(mut self) -> impl FusedIterator<Item = f32> {
+                    repeat_with(move || self.next_f32())
+                }
+
+                #[inline]
+                #[must_use]
+                pub fn next_f32_bounded(&mut self, bound: f32) -> f32 {
+                    assert!(
+                        bound > 0. && bound < f32::INFINITY,
+                        "bound must be finite and positive"
+                    );
+
+                    let r = self.next_f32() * bound;
+
+                    if r >= bound {
+                        f32::from_bits(bound.to_bits().wrapping_sub(1))
+                    } else {
+                        r
+                    }
+                }
+
+                #[inline]
+                pub fn try_next_f32_bounded(
+                    &mut self,
+                    bound: f32,
+                ) -> Result<f32, $crate::BoundError> {
+                    if bound > 0. && bound < f32::INFINITY {
+                        Ok(self.next_f32_bounded(bound))
+                    } else {
+                        Err($crate::BoundError::NotFiniteAndPositive)
+                    }
+                }
+
+                #[inline]
+                #[must_use]
+                pub fn next_f32_ranged(&mut self, range: Range<f32>) -> f32 {
+                    let Range {
+                        start: origin,
+                        end: bound,
+                    } = range;
+
+                    let mut r = self.next_f32();
+
+                    if origin < bound {
+                        r = r * (bound - origin) + origin;
+
+                        if r >= bound {
+                            r = f32::from_bits(bound.to_bits().wrapping_sub(1));
+                        }
+                    }
+
+                    r
+                }
+
+                #[inline]
+                pub fn try_next_f32_ranged(
+                    &mut self,
+                    range: Range<f32>,
+                ) -> Result<f32, $crate::BoundError> {
+                    if range.start < range.end && range.end - range.start < f32::INFINITY {
+                        Ok(self.next_f32_ranged(range))
+                    } else {
+                        Err($crate::BoundError::InvalidRange)
+                    }
+                }
+
+                #[inline]
+                pub fn f32_iter_ranged(
+                    &mut self,
+                    range: Range<f32>,
+                ) -> impl FusedIterator<Item = f32> + '_ {
+                    repeat_with(move || self.next_f32_ranged(range.clone()))
+                }
+
+                #[inline]
+                pub fn into_f32_iter_ranged(
+                    mut self,
+                    range: Range<f32>,
+                ) -> impl FusedIterator<Item = f32> {
+                    repeat_with(move || self.next_f32_ranged(range.clone()))
+                }
+
+                #[inline]
+                #[must_use]
+                pub fn next_f64(&mut self) -> f64 {
+                    let upper = (self.next(26) as i64) << 27;
+                    let lower = self.next(27) as i64;
+
+                    (upper.wrapping_add(lower) as f64) * consts::DOUBLE_UNIT
+                }
+
+                #[inline]
+                pub fn fill_f64(&mut self, dest: &mut [f64]) {
+                    dest.iter_mut().for_each(|x| *x = self.next_f64());
+                }
+
+                #[inline]
+                #[must_use]
+                pub fn next_f64_bounded(&mut self, bound: f64) -> f64 {
+                    assert!(
+                        bound > 0. && bound < f64::INFINITY,
+                        "bound must be finite and positive"
+                    );
+
+                    let r = self.next_f64() * bound;
+
+                    if r >= bound {
+                        f64::from_bits(bound.to_bits() - 1)
+                    } else {
+                        r
+                    }
+                }
+
+                #[inline]
+                pub fn try_next_f64_bounded(
+                    &mut self,
+                    bound: f64,
+                ) -> Result<f64, $crate::BoundError> {
+                    if bound > 0. && bound < f64::INFINITY {
+                        Ok(self.next_f64_bounded(bound))
+                    } else {
+                        Err($crate::BoundError::NotFiniteAndPositive)
+                    }
+                }
+
+                #[inline]
+                #[must_use]
+                pub fn next_f64_ranged(&mut self, range: Range<f64>) -> f64 {
+                    let Range {
+                        start: origin,
+                        end: bound,
+                    } = range;
+
+                    let mut r = self.next_f64();
+
+                    if origin < bound {
+                        r = math::mul_add(r, bound - origin, origin);
+
+                        if r >= bound {
+                            r = f64::from_bits(r.to_bits().wrapping_sub(1));
+                        }
+                    }
+
+                    r
+                }
+
+                #[inline]
+                pub fn try_next_f64_ranged(
+                    &mut self,
+                    range: Range<f64>,
+                ) -> Result<f64, $crate::BoundError> {
+                    if range.start < range.end && range.end - range.start < f64::INFINITY {
+                        Ok(self.next_f64_ranged(range))
+                    } else {
+                        Err($crate::BoundError::InvalidRange)
+                    }
+                }
+
+                #[inline]
+                pub fn f64_iter(&mut self) -> impl FusedIterator<Item = f64> + '_ {
+                    repeat_with(|| self.next_f64())
+                }
+
+                #[inline]
+                pub fn into_f64_iter(mut self) -> impl FusedIterator<Item = f64> {
+                    repeat_with(move || self.next_f64())
+                }
+
+                #[inline]
+                pub fn f64_iter_bounded(
+                    &mut self,
+                    bound: f64,
+                ) -> impl FusedIterator<Item = f64> + '_ {
+                    repeat_with(move || self.next_f64_bounded(bound))
+                }
+
+                #[inline]
+                pub fn into_f64_iter_bounded(
+                    mut self,
+                    bound: f64,
+                ) -> impl FusedIterator<Item = f64> {
+                    repeat_with(move || self.next_f64_bounded(bound))
+                }
+
+                #[inline]
+                pub fn f64_iter_ranged(
+                    &mut self,
+                    range: Range<f64>,
+                ) -> impl FusedIterator<Item = f64> + '_ {
+                    repeat_with(move || self.next_f64_ranged(range.clone()))
+                }
+
+                #[inline]
+                pub fn into_f64_iter_ranged(
+                    mut self,
+                    range: Range<f64>,
+                ) -> impl FusedIterator<Item = f64> {
+                    repeat_with(move || self.next_f64_ranged(range.clone()))
+                }
+
+                #[inline]
+                pub fn f64_stream(
+                    &mut self,
+                    len: usize,
+                ) -> impl ExactSizeIterator<Item = f64> + FusedIterator + '_ {
+                    (0..len).map(move |_| self.next_f64())
+                }
+
+                #[inline]
+                pub fn f64_stream_bounded(
+                    &mut self,
+                    len: usize,
+                    bound: f64,
+                ) -> impl ExactSizeIterator<Item = f64> + FusedIterator + '_ {
+                    (0..len).map(move |_| self.next_f64_bounded(bound))
+                }
+
+                #[inline]
+                pub fn f64_stream_ranged(
+                    &mut self,
+                    len: usize,
+                    range: Range<f64>,
+                ) -> impl ExactSizeIterator<Item = f64> + FusedIterator + '_ {
+                    (0..len).map(move |_| self.next_f64_ranged(range.clone()))
+                }
+
+                /// Generate a gaussian like `nextGaussian`, computed with
+                /// [`Strict`]($crate::Strict) math so it matches Java exactly.
+                #[inline]
+                #[must_use]
+                pub fn next_gaussian(&mut self) -> f64 {
+                    self.next_gaussian_using::<$crate::Strict>()
+                }
+
+                /// Generate a gaussian like [`Self::next_gaussian`], computed
+                /// with the math of `P`.
+                ///
+                /// A cached gaussian is returned as is, whichever policy it was
+                /// computed with.
+                #[inline]
+                #[must_use]
+                pub fn next_gaussian_using<P: $crate::MathPolicy>(&mut self) -> f64 {
+                    if let Some(next) = self.next_gaussian.take() {
+                        return next;
+                    }
+
+                    let (v1, v2) = self.polar_gaussians::<P>();
+
+                    self.next_gaussian = Some(v2);
+
+                    v1
+                }
+
+                /// Generate the next two gaussians, exactly as two calls to
+                /// [`Self::next_gaussian`] would.
+                ///
+                /// With no gaussian cached, this returns both gaussians of one
+                /// draw and leaves none cached, rather than caching the second
+                /// only to take it straight back out. With one cached, that is
+                /// returned first, and the second of the new draw is cached.
+                #[inline]
+                #[must_use]
+                pub fn next_gaussian_pair(&mut self) -> (f64, f64) {
+                    match self.next_gaussian.take() {
+                        Some(first) => (first, self.next_gaussian()),
+                        None => self.polar_gaussians::<$crate::Strict>(),
+                    }
+                }
+
+                /// Generate two gaussians as Q16.16 integers, without any
+                /// floating point, with [`fixed::gaussian_pair`]($crate::fixed::gaussian_pair).
+                ///
+                /// These are not the gaussians of [`Self::next_gaussian`], and
+                /// the cached gaussian is left alone.
+                #[inline]
+                #[must_use]
+                pub fn next_gaussian_pair_q16(&mut self) -> (i32, i32) {
+                    $crate::fixed::gaussian_pair(|| self.next_i32())
+                }
+
+                /// Draw two gaussians with the polar method, like
+                /// `nextGaussian`.
+                #[inline]
+                fn polar_gaussians<P: $crate::MathPolicy>(&mut self) -> (f64, f64) {
+                    repeat_with(|| {
+                        let v1 = math::mul_add(2., self.next_f64(), -1.);
+                        let v2 = math::mul_add(2., self.next_f64(), -1.);
+                        let s = (v1 * v1) + (v2 * v2);
+
+                        (v1, v2, s)
+                    })
+                    .find(|(.., s)| *s < 1. && *s != 0.)
+                    .map(|(v1, v2, s)| {
+                        let multiplier = P::sqrt(-2. * P::ln(s) / s);
+
+                        (v1 * multiplier, v2 * multiplier)
+                    })
+                    .expect("failed to generate next gaussian values")
+                }
+
+                #[inline]
+                pub fn gaussian_iter(&mut self) -> impl FusedIterator<Item = f64> + '_ {
+                    repeat_with(|| self.next_gaussian())
+                }
+
+                #[inline]
+                pub fn into_gaussian_iter(mut self) -> impl FusedIterator<Item = f64> {
+                    repeat_with(move || self.next_gaussian())
+                }
+
+                /// Like Java's `nextGaussian(mean, stddev)`, which draws from
+                /// the ziggurat over `nextLong()` rather than scaling
+                /// `nextGaussian()`, so the cached gaussian is left alone.
+                #[inline]
+                #[must_use]
+                pub fn next_gaussian_with(&mut self, mean: f64, stddev: f64) -> f64 {
+                    assert!(
+                        stddev >= 0. || stddev.is_nan(),
+                        "standard deviation must be non-negative"
+                    );
+
+                    mean + stddev * $crate::ziggurat::next_gaussian(|| self.next_i64())
+                }
+
+                /// Like Java's `nextExponential()`, which draws from the
+                /// ziggurat over `nextLong()`.
+                #[inline]
+                #[must_use]
+                pub fn next_exponential(&mut self) -> f64 {
+                    $crate::ziggurat::next_exponential(|| self.next_i64())
+                }
+
+                /// Generate an index below `len`, like
+                /// [`Self::next_i32_bounded`] when `len` fits in an `i32`, and
+                /// like [`Self::next_i64_bounded`] otherwise.
+                ///
+                /// # Panics
+                ///
+                /// Panics if `len` is zero or does not fit in an `i64`.
+                #[inline]
+                #[must_use]
+                pub fn next_index(&mut self, len: usize) -> usize {
+                    if let Ok(len) = i32::try_from(len) {
+                        self.next_i32_bounded(len) as usize
+                    } else {
+                        let len = i64::try_from(len).expect("length is too long");
+
+                        self.next_i64_bounded(len) as usize
+                    }
+                }
+
+                /// Generate a `usize` in `range`, like
+                /// [`Self::next_i32_ranged`] when the range fits in an `i32`,
+                /// and like [`Self::next_i64_ranged`] otherwise.
+                ///
+                /// # Panics
+                ///
+                /// Panics if `range` is empty or ends above `i64::MAX`.
+                #[inline]
+                #[must_use]
+                pub fn next_usize_ranged(&mut self, range: Range<usize>) -> usize {
+                    <usize as $crate::UniformJava>::sample_java(self, range)
+                }
+
+                /// Generate a `char` in `range` like `(char) nextInt(start, end)`,
+                /// drawing again whenever the result is a surrogate.
+                ///
+                /// # Panics
+                ///
+                /// Panics if `range` is empty.
+                #[inline]
+                #[must_use]
+                pub fn next_char(&mut self, range: Range<char>) -> char {
+                    self.next_range(range)
+                }
+
+                /// Pick a character of `A-Z`, `a-z` and `0-9`, in that order,
+                /// with [`Self::next_i32_bounded`].
+                #[inline]
+                #[must_use]
+                pub fn next_ascii_alphanumeric(&mut self) -> char {
+                    const CHARSET: &[u8; 62] =
+                        b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+
+                    CHARSET[self.next_i32_bounded(CHARSET.len() as i32) as usize] as char
+                }
+
+                /// Generate a string of `len` characters picked from `charset`
+                /// with [`Self::choose`].
+                ///
+                /// # Panics
+                ///
+                /// Panics if `charset` is empty.
+                #[cfg(feature = "std")]
+                pub fn next_string(&mut self, len: usize, charset: &[char]) -> std::string::String {
+                    (0..len)
+                        .map(|_| *self.choose(charset).expect("charset must not be empty"))
+                        .collect()
+                }
+
+                /// Generate the bytes of a version 4 UUID like Java's
+                /// `UUID.randomUUID()`, which sets the version and variant bits
+                /// of 16 random bytes.
+                #[inline]
+                #[must_use]
+                pub fn next_uuid(&mut self) -> [u8; 16] {
+                    let mut bytes = [0; 16];
+                    self.next_bytes(&mut bytes);
+
+                    bytes[6] = bytes[6] & 0x0f | 0x40;
+                    bytes[8] = bytes[8] & 0x3f | 0x80;
+
+                    bytes
+                }
+
+                /// Like [`Self::next_uuid`], as a [`uuid::Uuid`].
+                #[inline]
+                #[must_use]
+                #[cfg(feature = "uuid")]
+                pub fn next_uuid_v4(&mut self) -> ::uuid::Uuid {
+                    ::uuid::Uuid::from_bytes(self.next_uuid())
+                }
+
+                /// Generate the big-endian magnitude of a random integer below
+                /// `2^num_bits`, like Java's `new BigInteger(numBits, rnd)`.
+                ///
+                /// Java draws `ceil(num_bits / 8)` bytes with `nextBytes` and
+                /// masks the excess bits off the first one.
+                #[cfg(feature = "std")]
+                pub fn next_big_uint_bits(&mut self, num_bits: u32) -> std::vec::Vec<u8> {
+                    let mut bytes = std::vec![0; (num_bits as usize).div_ceil(8)];
+
+                    if !bytes.is_empty() {
+                        self.next_bytes(&mut bytes);
+
+                        let excess_bits = 8 * bytes.len() as u32 - num_bits;
+                        bytes[0] &= u8::MAX >> excess_bits;
+                    }
+
+                    bytes
+                }
+
+                /// Like [`Self::next_big_uint_bits`], as a
+                /// [`num_bigint::BigUint`].
+                #[cfg(feature = "num-bigint")]
+                pub fn next_biguint_bits(&mut self, num_bits: u32) -> ::num_bigint::BigUint {
+                    ::num_bigint::BigUint::from_bytes_be(&self.next_big_uint_bits(num_bits))
+                }
+
+                /// Generate a probable prime of `bit_length` bits like Java's
+                /// `BigInteger.probablePrime(bitLength, rnd)`.
+                ///
+                /// The chance of a composite is at most `2^-100`.
+                ///
+                /// # Panics
+                ///
+                /// Panics if `bit_length` is less than 2.
+                #[cfg(feature = "num-bigint")]
+                #[must_use]
+                pub fn next_probable_prime(&mut self, bit_length: u32) -> ::num_bigint::BigUint {
+                    $crate::prime::probable_prime(
+                        self,
+                        bit_length,
+                        $crate::prime::DEFAULT_PRIME_CERTAINTY,
+                    )
+                }
+
+                /// Generate a probable prime of `bit_length` bits like Java's
+                /// `new BigInteger(bitLength, certainty, rnd)`.
+                ///
+                /// The chance of a composite is at most `2^-certainty`, and
+                /// draws are made exactly as Java's would be, candidates and
+                /// Miller-Rabin bases alike, so a seeded generator gives the
+                /// same prime as it does in Java.
+                ///
+                /// # Panics
+                ///
+                /// Panics if `bit_length` is less than 2.
+                #[cfg(feature = "num-bigint")]
+                #[must_use]
+                pub fn next_prime_with_certainty(
+                    &mut self,
+                    bit_length: u32,
+                    certainty: i32,
+                ) -> ::num_bigint::BigUint {
+                    $crate::prime::probable_prime(self, bit_length, certainty)
+                }
+
+                /// Shuffle `slice` like Java's `Collections.shuffle(list, rnd)`.
+                ///
+                /// # Panics
+                ///
+                /// Panics if `slice` is longer than a Java list can be.
+                #[inline]
+                pub fn shuffle<T>(&mut self, slice: &mut [T]) {
+                    assert!(slice.len() <= i32::MAX as usize, "slice is too long");
+
+                    for i in (2..=slice.len()).rev() {
+                        slice.swap(i - 1, self.next_i32_bounded(i as i32) as usize);
+                    }
+                }
+
+                /// Fill `dest` with a random permutation of `0..dest.len()`,
+                /// like shuffling the list of indices in order with
+                /// `Collections.shuffle(list, rnd)` in Java.
+                ///
+                /// # Panics
+                ///
+                /// Panics if `dest` is longer than a Java list can be.
+                #[inline]
+                pub fn fill_permutation(&mut self, dest: &mut [usize]) {
+                    dest.iter_mut().enumerate().for_each(|(i, x)| *x = i);
+
+                    self.shuffle(dest);
+                }
+
+                /// Generate a random permutation of `0..n`, like
+                /// [`Self::fill_permutation`].
+                ///
+                /// # Panics
+                ///
+                /// Panics if `n` is more than a Java list can hold.
+                #[cfg(feature = "std")]
+                #[must_use]
+                pub fn permutation(&mut self, n: usize) -> std::vec::Vec<usize> {
+                    assert!(n <= i32::MAX as usize, "n is too large");
+
+                    let mut permutation = (0..n).collect::<std::vec::Vec<_>>();
+                    self.shuffle(&mut permutation);
+
+                    permutation
+                }
+
+                /// Pick a random element of `slice` like
+                /// `list.get(rnd.nextInt(list.size()))` in Java, or `None` if it
+                /// is empty.
+                ///
+                /// # Panics
+                ///
+                /// Panics if `slice` is longer than a Java list can be.
+                #[inline]
+                #[must_use]
+                pub fn choose<'a, T>(&mut self, slice: &'a [T]) -> Option<&'a T> {
+                    assert!(slice.len() <= i32::MAX as usize, "slice is too long");
+
+                    if slice.is_empty() {
+                        return None;
+                    }
+
+                    slice.get(self.next_i32_bounded(slice.len() as i32) as usize)
+                }
+
+                /// Like [`Self::choose`], returning a mutable reference.
+                #[inline]
+                #[must_use]
+                pub fn choose_mut<'a, T>(&mut self, slice: &'a mut [T]) -> Option<&'a mut T> {
+                    assert!(slice.len() <= i32::MAX as usize, "slice is too long");
+
+                    if slice.is_empty() {
+                        return None;
+                    }
+
+                    slice.get_mut(self.next_i32_bounded(slice.len() as i32) as usize)
+                }
+
+                /// Pick a random element of `slice` with probability
+                /// proportional to its `weight`, or `None` if the weights add up
+                /// to zero.
+                ///
+                /// Like the usual Java idiom, this draws
+                /// `next_i32_bounded(total)` and walks the cumulative weights
+                /// until they exceed it.
+                ///
+                /// # Panics
+                ///
+                /// Panics if the weights add up to more than `i32::MAX`.
+                #[inline]
+                #[must_use]
+                pub fn choose_weighted<'a, T>(
+                    &mut self,
+                    slice: &'a [T],
+                    weight: impl Fn(&T) -> u32,
+                ) -> Option<&'a T> {
+                    let total: u64 = slice.iter().map(|x| weight(x) as u64).sum();
+
+                    assert!(total <= i32::MAX as u64, "total weight is too large");
+
+                    if total == 0 {
+                        return None;
+                    }
+
+                    let mut r = self.next_i32_bounded(total as i32) as u32;
+
+                    slice.iter().find(|x| match r.checked_sub(weight(x)) {
+                        Some(rest) => {
+                            r = rest;
+                            false
+                        }
+                        None => true,
+                    })
+                }
+
+                /// Pick `amount` distinct indices below `len`, in the order
+                /// they were picked.
+                ///
+                /// This runs the first `amount` steps of a Fisher–Yates shuffle
+                /// of `0..len` from the front, swapping index `i` with
+                /// `i + next_i32_bounded(len - i)`.
+                ///
+                /// # Panics
+                ///
+                /// Panics if `amount` is greater than `len`, or if `len` is
+                /// longer than a Java list can be.
+                #[cfg(feature = "std")]
+                pub fn sample_indices(
+                    &mut self,
+                    len: usize,
+                    amount: usize,
+                ) -> std::vec::Vec<usize> {
+                    assert!(len <= i32::MAX as usize, "length is too long");
+                    assert!(amount <= len, "amount must not be greater than length");
+
+                    let mut indices: std::vec::Vec<usize> = (0..len).collect();
+
+                    for i in 0..amount {
+                        let j = i + self.next_i32_bounded((len - i) as i32) as usize;
+                        indices.swap(i, j);
+                    }
+
+                    indices.truncate(amount);
+                    indices
+                }
+
+                /// Pick `amount` distinct elements of `slice`, with the
+                /// indices of [`Self::sample_indices`].
+                #[cfg(feature = "std")]
+                pub fn sample<'a, T>(
+                    &mut self,
+                    slice: &'a [T],
+                    amount: usize,
+                ) -> std::vec::Vec<&'a T> {
+                    self.sample_indices(slice.len(), amount)
+                        .into_iter()
+                        .map(|i| &slice[i])
+                        .collect()
+                }
+
+                /// Generate a value in `range` with the Java algorithm for
+                /// its type.
+                ///
+                /// # Panics
+                ///
+                /// Panics if `range` is empty, or for floating-point types,
+                /// if its width is not finite.
+                #[inline]
+                #[must_use]
+                pub fn next_range<T: $crate::UniformJava>(&mut self, range: Range<T>) -> T {
+                    T::sample_java(self, range)
+                }
+            }
+
+            #[cfg(feature = "num-bigint")]
+            impl<$($generics)*> $crate::prime::Source for $ty {
+                #[inline]
+                fn next_i32(&mut self) -> i32 {
+                    self.next_i32()
+                }
+
+                #[inline]
+                fn random_bits(&mut self, num_bits: u32) -> ::num_bigint::BigUint {
+                    self.next_biguint_bits(num_bits)
+                }
+            }
+
+            impl<$($generics)*> $crate::RangedRandom for $ty {
+                #[inline]
+                fn next_i32_ranged(&mut self, range: Range<i32>) -> i32 {
+                    self.next_i32_ranged(range)
+                }
+
+                #[inline]
+                fn next_i64_ranged(&mut self, range: Range<i64>) -> i64 {
+                    self.next_i64_ranged(range)
+                }
+
+                #[inline]
+                fn next_f32_ranged(&mut self, range: Range<f32>) -> f32 {
+                    self.next_f32_ranged(range)
+                }
+
+                #[inline]
+                fn next_f64_ranged(&mut self, range: Range<f64>) -> f64 {
+                    self.next_f64_ranged(range)
+                }
+            }
+        };
+    };
+    ($ty:ty) => {
+        impl_random!([] $ty);
+    };
+}
@@ -5,15 +5,72 @@ mod stdmath {
         ::std::primitive::f64::mul_add(x, y, z)
     }
 
+    #[inline(always)]
+    #[allow(dead_code)]
+    pub(crate) fn mul_add_f32(x: f32, y: f32, z: f32) -> f32 {
+        ::std::primitive::f32::mul_add(x, y, z)
+    }
+
     #[inline(always)]
     pub(crate) fn ln(x: f64) -> f64 {
         ::std::primitive::f64::ln(x)
     }
 
+    #[inline(always)]
+    #[allow(dead_code)]
+    pub(crate) fn ln_f32(x: f32) -> f32 {
+        ::std::primitive::f32::ln(x)
+    }
+
+    #[inline(always)]
+    pub(crate) fn exp(x: f64) -> f64 {
+        ::std::primitive::f64::exp(x)
+    }
+
+    #[inline(always)]
+    #[allow(dead_code)]
+    pub(crate) fn exp_m1(x: f64) -> f64 {
+        ::std::primitive::f64::exp_m1(x)
+    }
+
+    #[inline(always)]
+    #[allow(dead_code)]
+    pub(crate) fn ln_1p(x: f64) -> f64 {
+        ::std::primitive::f64::ln_1p(x)
+    }
+
     #[inline(always)]
     pub(crate) fn sqrt(x: f64) -> f64 {
         ::std::primitive::f64::sqrt(x)
     }
+
+    #[inline(always)]
+    #[allow(dead_code)]
+    pub(crate) fn sqrt_f32(x: f32) -> f32 {
+        ::std::primitive::f32::sqrt(x)
+    }
+
+    #[inline(always)]
+    #[allow(dead_code)]
+    pub(crate) fn cbrt(x: f64) -> f64 {
+        ::std::primitive::f64::cbrt(x)
+    }
+
+    #[inline(always)]
+    #[allow(dead_code)]
+    pub(crate) fn hypot(x: f64, y: f64) -> f64 {
+        ::std::primitive::f64::hypot(x, y)
+    }
+
+    #[inline(always)]
+    pub(crate) fn sin(x: f64) -> f64 {
+        ::std::primitive::f64::sin(x)
+    }
+
+    #[inline(always)]
+    pub(crate) fn cos(x: f64) -> f64 {
+        ::std::primitive::f64::cos(x)
+    }
 }
 
 #[cfg(feature = "std")]
@@ -26,92 +83,450 @@ mod libm {
         ::libm::fma(x, y, z)
     }
 
+    #[inline(always)]
+    #[allow(dead_code)]
+    pub(crate) fn mul_add_f32(x: f32, y: f32, z: f32) -> f32 {
+        ::libm::fmaf(x, y, z)
+    }
+
     #[inline(always)]
     pub(crate) fn ln(x: f64) -> f64 {
         ::libm::log(x)
     }
 
+    #[inline(always)]
+    #[allow(dead_code)]
+    pub(crate) fn ln_f32(x: f32) -> f32 {
+        ::libm::logf(x)
+    }
+
+    #[inline(always)]
+    pub(crate) fn exp(x: f64) -> f64 {
+        ::libm::exp(x)
+    }
+
+    #[inline(always)]
+    #[allow(dead_code)]
+    pub(crate) fn exp_m1(x: f64) -> f64 {
+        ::libm::expm1(x)
+    }
+
+    #[inline(always)]
+    #[allow(dead_code)]
+    pub(crate) fn ln_1p(x: f64) -> f64 {
+        ::libm::log1p(x)
+    }
+
     #[inline(always)]
     pub(crate) fn sqrt(x: f64) -> f64 {
         ::libm::sqrt(x)
     }
+
+    #[inline(always)]
+    #[allow(dead_code)]
+    pub(crate) fn sqrt_f32(x: f32) -> f32 {
+        ::libm::sqrtf(x)
+    }
+
+    #[inline(always)]
+    #[allow(dead_code)]
+    pub(crate) fn cbrt(x: f64) -> f64 {
+        ::libm::cbrt(x)
+    }
+
+    #[inline(always)]
+    #[allow(dead_code)]
+    pub(crate) fn hypot(x: f64, y: f64) -> f64 {
+        ::libm::hypot(x, y)
+    }
+
+    #[inline(always)]
+    pub(crate) fn sin(x: f64) -> f64 {
+        ::libm::sin(x)
+    }
+
+    #[inline(always)]
+    pub(crate) fn cos(x: f64) -> f64 {
+        ::libm::cos(x)
+    }
 }
 
 #[cfg(all(not(feature = "std"), feature = "libm"))]
 pub(crate) use libm::*;
 
+#[cfg(feature = "table-math")]
+pub(crate) mod table;
+
 #[allow(dead_code)]
 mod fallback {
-    use core::num::FpCategory;
+    use crate::strictmath;
+
+    /// Split a finite, nonzero `x` into its sign, integer significand and
+    /// exponent.
+    #[inline(always)]
+    fn decompose(x: f64) -> (bool, u128, i32) {
+        let bits = x.to_bits();
+        let biased = ((bits >> 52) & 0x7ff) as i32;
+        let fraction = bits & ((1 << 52) - 1);
+
+        match biased {
+            0 => (x < 0.0, fraction as u128, -1074),
+            _ => (x < 0.0, (fraction | 1 << 52) as u128, biased - 1075),
+        }
+    }
 
+    /// Shift `x` right by `n`, folding the bits shifted out into the lowest
+    /// bit so they still count when rounding.
     #[inline(always)]
+    fn shr_sticky(x: u128, n: u32) -> u128 {
+        match n {
+            0 => x,
+            1..=127 => (x >> n) | (x & ((1 << n) - 1) != 0) as u128,
+            _ => (x != 0) as u128,
+        }
+    }
+
+    /// `x * y + z` with a single rounding, in software.
+    ///
+    /// The product is exact in 106 bits, so both terms are lined up in a
+    /// `u128` with the bits below it folded into a sticky bit, and the sum
+    /// is rounded to nearest, ties to even, once.
+    #[inline]
     pub(crate) fn mul_add(x: f64, y: f64, z: f64) -> f64 {
-        (x * y) + z
+        if !x.is_finite() || !y.is_finite() || x == 0.0 || y == 0.0 {
+            // The product is exact, so only the sum rounds.
+            return (x * y) + z;
+        } else if !z.is_finite() {
+            // The exact product is finite, even if it rounds to infinity.
+            return z + z;
+        } else if z == 0.0 {
+            // The exact product is nonzero, so its sign wins.
+            return x * y;
+        }
+
+        let (sx, mx, ex) = decompose(x);
+        let (sy, my, ey) = decompose(y);
+        let (sz, mz, ez) = decompose(z);
+
+        // Put the top bit of both terms at bit 125, leaving room to carry,
+        // and line them up.
+        let p = mx * my;
+        let (p, ep) = (
+            p << (p.leading_zeros() - 2),
+            ex + ey - (p.leading_zeros() - 2) as i32,
+        );
+        let (z, ez) = (
+            mz << (mz.leading_zeros() - 2),
+            ez - (mz.leading_zeros() - 2) as i32,
+        );
+
+        let e = ep.max(ez);
+        let p = shr_sticky(p, (e - ep) as u32);
+        let z = shr_sticky(z, (e - ez) as u32);
+
+        let sp = sx != sy;
+        let (negative, r) = match (sp == sz, p >= z) {
+            (true, _) => (sp, p + z),
+            (false, true) => (sp, p - z),
+            (false, false) => (sz, z - p),
+        };
+
+        if r == 0 {
+            return 0.0;
+        }
+
+        // r * 2^e, with its top bit worth 2^(top + e).
+        let top = 127 - r.leading_zeros() as i32;
+        let biased = top + e + 1023;
+
+        if biased >= 0x7ff {
+            return if negative {
+                f64::NEG_INFINITY
+            } else {
+                f64::INFINITY
+            };
+        }
+
+        // Keep 53 bits, or as many as a subnormal holds.
+        let shift = if biased > 0 { top - 52 } else { -1074 - e };
+
+        let m = if shift <= 0 {
+            // Cancellation left few enough bits to be exact.
+            (r << -shift) as u64
+        } else if shift >= 128 {
+            0
+        } else {
+            let shift = shift as u32;
+            let m = (r >> shift) as u64;
+            let rem = r & ((1 << shift) - 1);
+            let half = 1 << (shift - 1);
+
+            m + (rem > half || (rem == half && m & 1 == 1)) as u64
+        };
+
+        // m carries its implicit bit into the exponent, including when
+        // rounding made it 2^53, and a subnormal rounded up to 2^52 becomes
+        // the least normal.
+        let bits = match biased {
+            1.. => (((biased - 1) as u64) << 52) + m,
+            _ => m,
+        };
+
+        f64::from_bits(bits | (negative as u64) << 63)
     }
 
-    const MANTISSA_MASK: u64 = (1 << 52) - 1;
-    const EXP: u64 = 1023 << 52;
+    /// `x * y + z` with a single rounding, computed in `f64`.
+    ///
+    /// The product is exact there, and the sum is rounded to odd, stepping
+    /// an even result towards the error of the addition, so rounding it
+    /// again to `f32` gives the correctly rounded result.
+    #[inline]
+    pub(crate) fn mul_add_f32(x: f32, y: f32, z: f32) -> f32 {
+        let (xy, z) = (x as f64 * y as f64, z as f64);
+        let sum = xy + z;
+
+        // The rounding error of the sum, exactly.
+        let z_part = sum - xy;
+        let error = (xy - (sum - z_part)) + (z - z_part);
+
+        if sum.is_finite() && error != 0.0 && sum.to_bits() & 1 == 0 {
+            let bits = if (error > 0.0) == (sum > 0.0) {
+                sum.to_bits() + 1
+            } else {
+                sum.to_bits() - 1
+            };
 
-    const COEFFICIENTS: [f64; 5] = [
-        -0.081615808498122389,
-        0.64514236358772081,
-        -2.1206751311142673,
-        4.0700907918522011,
-        -2.5128546239033374,
-    ];
+            f64::from_bits(bits) as f32
+        } else {
+            sum as f32
+        }
+    }
 
-    /// Inspiration: https://gist.github.com/LingDong-/7e4c4cae5cbbc44400a05fba65f06f23
+    /// The fdlibm port rather than an approximation, so gaussians match
+    /// Java's exactly without `std` or `libm`, at the cost of a division and
+    /// a polynomial of degree 7 in `s^2`.
     #[inline(always)]
-    pub(crate) fn log2(mut x: f64) -> f64 {
-        match x.classify() {
-            FpCategory::Normal if x.is_sign_positive() => {
-                let mut bits = x.to_bits();
+    pub(crate) fn ln(x: f64) -> f64 {
+        strictmath::log(x)
+    }
 
-                let exp = (((bits >> 52) & 0x7ff) as i32).wrapping_sub(0x3ff);
+    /// The natural logarithm of `x`, a port of FreeBSD's `__ieee754_logf`,
+    /// accurate to under an ulp.
+    #[inline]
+    pub(crate) fn ln_f32(mut x: f32) -> f32 {
+        const LN2_HI: f32 = 6.931381e-1;
+        const LN2_LO: f32 = 9.058001e-6;
+        const TWO25: f32 = 3.3554432e7;
+        const LG: [f32; 4] = [6.666666e-1, 4.0000972e-1, 2.8498787e-1, 2.4279079e-1];
 
-                bits &= MANTISSA_MASK;
-                bits |= EXP;
+        let mut ix = x.to_bits() as i32;
+        let mut k = 0;
 
-                x = f64::from_bits(bits);
+        if ix < 0x0080_0000 {
+            if ix & 0x7fff_ffff == 0 {
+                return f32::NEG_INFINITY;
+            } else if ix < 0 {
+                return f32::NAN;
+            }
 
-                let mut u = COEFFICIENTS[0];
+            // Subnormal, scale it up.
+            k -= 25;
+            x *= TWO25;
+            ix = x.to_bits() as i32;
+        }
 
-                for coeff in COEFFICIENTS.into_iter().skip(1) {
-                    u *= x;
-                    u += coeff;
-                }
+        if ix >= 0x7f80_0000 {
+            return x + x;
+        }
+
+        k += (ix >> 23) - 127;
+        ix &= 0x007f_ffff;
+
+        // Normalize x or x / 2 into [sqrt(2) / 2, sqrt(2)).
+        let i = (ix + (0x95f64 << 3)) & 0x0080_0000;
+
+        x = f32::from_bits((ix | (i ^ 0x3f80_0000)) as u32);
+        k += i >> 23;
+
+        let f = x - 1.0;
+        let dk = k as f32;
+
+        if (0x007f_ffff & (0x8000 + ix)) < 0xc000 {
+            // |f| < 2^-9
+            if f == 0.0 {
+                return if k == 0 {
+                    0.0
+                } else {
+                    dk * LN2_HI + dk * LN2_LO
+                };
+            }
+
+            let r = f * f * (0.5 - 0.333_333_34 * f);
+
+            return if k == 0 {
+                f - r
+            } else {
+                dk * LN2_HI - ((r - dk * LN2_LO) - f)
+            };
+        }
+
+        let s = f / (2.0 + f);
+        let z = s * s;
+        let w = z * z;
+        let r = z * (LG[0] + w * LG[2]) + w * (LG[1] + w * LG[3]);
+
+        if ((ix - (0x6147a << 3)) | ((0x6b851 << 3) - ix)) > 0 {
+            let hfsq = 0.5 * f * f;
 
-                u + exp.abs() as f64
+            if k == 0 {
+                f - (hfsq - s * (hfsq + r))
+            } else {
+                dk * LN2_HI - ((hfsq - (s * (hfsq + r) + dk * LN2_LO)) - f)
             }
-            FpCategory::Subnormal | FpCategory::Normal => f64::NAN,
-            FpCategory::Nan | FpCategory::Infinite => x,
-            FpCategory::Zero => -1. / (x * x),
+        } else if k == 0 {
+            f - s * (f - r)
+        } else {
+            dk * LN2_HI - ((s * (f - r) - dk * LN2_LO) - f)
         }
     }
 
     #[inline(always)]
-    pub(crate) fn ln(x: f64) -> f64 {
-        log2(x) * ::core::f64::consts::LN_2
+    pub(crate) fn exp(x: f64) -> f64 {
+        strictmath::exp(x)
+    }
+
+    #[inline(always)]
+    pub(crate) fn exp_m1(x: f64) -> f64 {
+        strictmath::expm1(x)
+    }
+
+    #[inline(always)]
+    pub(crate) fn ln_1p(x: f64) -> f64 {
+        strictmath::log1p(x)
     }
 
-    /// Inspiration: https://suraj.sh/fast-square-root-approximation
     #[inline(always)]
     pub(crate) fn sqrt(x: f64) -> f64 {
-        /// bit repr of 0.04303566602796716
-        const BIT_HACK: u64 = 0x1ff7a7dceaaec900;
+        strictmath::sqrt(x)
+    }
+
+    /// The correctly rounded square root of `x`, the same way as
+    /// [`strictmath::sqrt`] but on a 24-bit significand.
+    #[inline]
+    pub(crate) fn sqrt_f32(x: f32) -> f32 {
+        const IMPLICIT: u32 = 1 << 23;
+
+        if x.is_nan() {
+            return x + x;
+        } else if x == 0.0 || x == f32::INFINITY {
+            return x;
+        } else if x < 0.0 {
+            return f32::NAN;
+        }
+
+        let bits = x.to_bits();
+        let biased = (bits >> 23) as i32;
+
+        // x is m * 2^e, with m normalized to 24 bits.
+        let (mut m, mut e) = if biased == 0 {
+            let shift = (bits.leading_zeros() - 8) as i32;
+
+            (bits << shift, -149 - shift)
+        } else {
+            ((bits & (IMPLICIT - 1)) | IMPLICIT, biased - 150)
+        };
+
+        // Make e - 23 even, so it can be halved.
+        if e & 1 == 0 {
+            m <<= 1;
+            e -= 1;
+        }
+
+        let n = (m as u64) << 23;
+        let r = n.isqrt();
+        let r = (r + (n - r * r > r) as u64) as u32;
 
-        let mut bits = x.to_bits();
-        bits = BIT_HACK.wrapping_add(bits >> 1);
+        let exponent = (e - 23) / 2 + 23 + 127 - 1;
 
-        let mut y = f64::from_bits(bits);
+        f32::from_bits(((exponent as u32) << 23) + r)
+    }
+
+    #[inline(always)]
+    pub(crate) fn cbrt(x: f64) -> f64 {
+        strictmath::cbrt(x)
+    }
+
+    #[inline(always)]
+    pub(crate) fn hypot(x: f64, y: f64) -> f64 {
+        strictmath::hypot(x, y)
+    }
 
-        y = (y + (x / y)) * 0.5;
-        y = (y + (x / y)) * 0.5;
-        y = (y + (x / y)) * 0.5;
+    #[inline(always)]
+    pub(crate) fn sin(x: f64) -> f64 {
+        strictmath::sin(x)
+    }
 
-        y
+    #[inline(always)]
+    pub(crate) fn cos(x: f64) -> f64 {
+        strictmath::cos(x)
     }
 }
 
 #[cfg(not(any(feature = "std", feature = "libm")))]
 pub(crate) use fallback::*;
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "sse2"
+))]
+mod x86 {
+    use core::arch::asm;
+
+    use cfg_if::cfg_if;
+
+    cpufeatures::new!(cpuid_fma, "fma");
+
+    cfg_if! {
+        if #[cfg(feature = "std")] {
+            use super::stdmath as exact;
+        } else if #[cfg(feature = "libm")] {
+            use super::libm as exact;
+        } else {
+            use super::fallback as exact;
+        }
+    }
+
+    /// `x * y + z` with a single rounding, with the FMA instruction when the
+    /// CPU has it.
+    ///
+    /// Without `+fma` at compile time, `f64::mul_add` and `libm::fma` may be
+    /// exact software routines, so this checks for it once at runtime and
+    /// only falls back to them on CPUs without it. Either way the result is
+    /// the same.
+    #[inline(always)]
+    pub(crate) fn mul_add(mut x: f64, y: f64, z: f64) -> f64 {
+        if !cpuid_fma::get() {
+            return exact::mul_add(x, y, z);
+        }
+
+        // SAFETY: The CPU supports FMA. Unlike a `#[target_feature]`
+        // function, this can be inlined into callers compiled without it.
+        unsafe {
+            asm!(
+                "vfmadd213sd {x}, {y}, {z}",
+                x = inout(xmm_reg) x,
+                y = in(xmm_reg) y,
+                z = in(xmm_reg) z,
+                options(pure, nomem, nostack, preserves_flags),
+            );
+        }
+
+        x
+    }
+}
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "sse2"
+))]
+pub(crate) use x86::mul_add;
@@ -0,0 +1,306 @@
+//! `ln` and `sqrt` from small tables, refined with a polynomial and Newton's
+//! method, which need no division and so are much faster than the fdlibm
+//! ports where floating point is done in software.
+
+const LN2_HI: f64 = 6.931471803691238e-1;
+const LN2_LO: f64 = 1.9082149292705877e-10;
+
+/// 2<sup>54</sup>, to scale subnormals up by.
+const TWO54: f64 = 1.8014398509481984e16;
+
+const FRACTION_MASK: u64 = (1 << 52) - 1;
+
+/// The natural logarithm of `x`.
+///
+/// The significand is taken into `[sqrt(2) / 2, sqrt(2)]` and split as
+/// `c * (1 + r)`, with `c` the nearest multiple of `1 / 256` and `|r|` at
+/// most `2^-8.5`, so `ln(1 + r)` only needs six terms of its series. The
+/// result is within a few ulps of the exact one.
+#[inline]
+pub(crate) fn ln(x: f64) -> f64 {
+    const C: [f64; 5] = [
+        0.5,
+        0.333_333_333_333_333_3,
+        0.25,
+        0.2,
+        0.166_666_666_666_666_66,
+    ];
+
+    if x.is_nan() || x == f64::INFINITY {
+        return x + x;
+    } else if x == 0.0 {
+        return f64::NEG_INFINITY;
+    } else if x < 0.0 {
+        return f64::NAN;
+    }
+
+    let (x, k) = if x < f64::MIN_POSITIVE {
+        (x * TWO54, -54)
+    } else {
+        (x, 0)
+    };
+
+    let bits = x.to_bits();
+    let mut k = k + (bits >> 52) as i32 - 1023;
+    let mut m = f64::from_bits((bits & FRACTION_MASK) | 1.0f64.to_bits());
+
+    if m > ::core::f64::consts::SQRT_2 {
+        m *= 0.5;
+        k += 1;
+    }
+
+    let j = (m * 256.0 + 0.5) as usize;
+
+    // m and c are close enough that m - c is exact.
+    let r = (m - j as f64 * (1.0 / 256.0)) * INV[j - 181];
+    let p = r - r * r * (C[0] - r * (C[1] - r * (C[2] - r * (C[3] - r * C[4]))));
+    let dk = k as f64;
+
+    dk * LN2_HI + (LN[j - 181] + (p + dk * LN2_LO))
+}
+
+/// The square root of `x`.
+///
+/// The significand is taken into `[1, 4)`, and `1 / sqrt` of it is looked
+/// up to within `2^-8`, refined with two Newton steps, and multiplied back
+/// with a final correction. The result is within an ulp of the exact one.
+#[inline]
+pub(crate) fn sqrt(x: f64) -> f64 {
+    if x.is_nan() {
+        return x + x;
+    } else if x == 0.0 || x == f64::INFINITY {
+        return x;
+    } else if x < 0.0 {
+        return f64::NAN;
+    }
+
+    let (x, k) = if x < f64::MIN_POSITIVE {
+        (x * TWO54, -54)
+    } else {
+        (x, 0)
+    };
+
+    let bits = x.to_bits();
+    let e = k + (bits >> 52) as i32 - 1023;
+    let mut m = f64::from_bits((bits & FRACTION_MASK) | 1.0f64.to_bits());
+
+    // Make the exponent even, so it can be halved.
+    if e & 1 != 0 {
+        m *= 2.0;
+    }
+
+    let j = (m * 64.0 + 0.5) as usize;
+    let mut g = RSQRT[j - 64];
+
+    for _ in 0..2 {
+        g *= 1.5 - 0.5 * m * g * g;
+    }
+
+    let y = m * g;
+    let y = y + 0.5 * g * (m - y * y);
+
+    y * f64::from_bits((((e >> 1) + 1023) as u64) << 52)
+}
+
+/// `ln(j / 256)` for `j` in `181..=362`, covering `[sqrt(2) / 2, sqrt(2))`.
+#[rustfmt::skip]
+const LN: [f64; 182] = [
+    -0.3466804132137367, -0.34117075740276714, -0.33569129163814154,
+    -0.33024168687057687, -0.32482161940123766, -0.3194307707663612,
+    -0.31406882762497584, -0.3087354816496133, -0.3034304294199201,
+    -0.29815337231907635, -0.2929040164329326, -0.2876820724517809,
+    -0.2824872555746769, -0.27731928541623435, -0.27217788591581565,
+    -0.26706278524904525, -0.26197371574157396, -0.2569104137850272,
+    -0.2518726197550701, -0.24686007793152578, -0.24187253642048673,
+    -0.2369097470783577, -0.23197146543777514, -0.22705745063534608,
+    -0.2221674653411543, -0.2173012756899814, -0.2124586512141934,
+    -0.2076393647782445, -0.20284319251475147, -0.1980699137620938,
+    -0.19331931100349597, -0.18859116980755003, -0.18388527877013736,
+    -0.179201429457711, -0.17453941635189968, -0.16989903679539747,
+    -0.16528009093910292, -0.16068238169047347, -0.15610571466306167,
+    -0.15154989812720093, -0.14701474296180966, -0.14250006260728304,
+    -0.13800567301944372, -0.13353139262452263, -0.12907704227514236,
+    -0.1246424452072766, -0.1202274269981598, -0.1158318155251217,
+    -0.11145544092532282, -0.1070981355563671, -0.10275973395776894,
+    -0.09844007281325252, -0.09413899091386191, -0.08985632912186105,
+    -0.08559193033540351, -0.0813456394539524, -0.07711730334443129,
+    -0.07290677080808779, -0.06871389254805181, -0.06453852113757118,
+    -0.06038051098890748, -0.05623971832287608, -0.05211600113901402,
+    -0.048009219186360606, -0.04391923393483549, -0.039845908547199674,
+    -0.03578910785158528, -0.0317486983145803, -0.027724548014854862,
+    -0.023716526617316044, -0.01972450534777859, -0.015748356968139168,
+    -0.01178795575204224, -0.007843177461025893, -0.003913899321136329,
+    0.0, 0.003898640415657323, 0.007782140442054949,
+    0.011650617219975274, 0.015504186535965254, 0.019342962843130935,
+    0.02316705928153438, 0.026976587698202076, 0.030771658666753687,
+    0.034552381506659735, 0.0383188643021366, 0.04207121392068706,
+    0.0458095360312942, 0.04953393512227663, 0.053244514518812285,
+    0.056941376400138424, 0.06062462181643484, 0.06429435070539725,
+    0.06795066190850775, 0.07159365318700882, 0.07522342123758753,
+    0.07884006170777602, 0.08244366921107459, 0.08603433734180316,
+    0.08961215868968714, 0.0931772248541833, 0.09672962645855111,
+    0.10026945316367515, 0.10379679368164356, 0.10731173578908805,
+    0.11081436634029011, 0.11430477128005863, 0.11778303565638346,
+    0.12124924363286968, 0.12470347850095724, 0.12814582269193003,
+    0.13157635778871926, 0.13499516453750482, 0.13840232285911913,
+    0.14179791186025734, 0.1451820098444979, 0.14855469432313714,
+    0.15191604202584197, 0.15526612891112396, 0.15860503017663857,
+    0.16193282026931324, 0.16524957289530717, 0.16855536102980667,
+    0.17185025692665923, 0.17513433212784915, 0.1784076574728183,
+    0.18167030310763468, 0.184922338494012, 0.188163832418183,
+    0.19139485299962947, 0.19461546769967167, 0.19782574332991987,
+    0.20102574606059073, 0.2042155414286909, 0.2073951943460706,
+    0.21056476910734964, 0.21372432939771813, 0.21687393830061436,
+    0.2200136583052821, 0.22314355131420976, 0.22626367865045338,
+    0.22937410106484582, 0.23247487874309405, 0.2355660713127669,
+    0.238647737850175, 0.24171993688714516, 0.24478272641769092,
+    0.24783616390458127, 0.25088030628580943, 0.25391520998096345,
+    0.2569409308975004, 0.25995752443692605, 0.26296504550088134,
+    0.26596354849713794, 0.26895308734550394, 0.27193371548364176,
+    0.2749054858727992, 0.2778684510034563, 0.2808226629008878,
+    0.2837681731306446, 0.2867050328039543, 0.28963329258304266,
+    0.29255300268637746, 0.2954642128938359, 0.2983669725517973,
+    0.3012613305781618, 0.3041473354672967, 0.3070250352949119,
+    0.3098944777228647, 0.3127557100038969, 0.31560877898630335,
+    0.3184537311185346, 0.3212906124537343, 0.324119468654212,
+    0.32694034499585334, 0.329753286372468, 0.3325583373000766,
+    0.3353555419211378, 0.3381449440087164, 0.3409265869705932,
+    0.34370051385331846, 0.34646676734620857,
+];
+
+/// `256 / j` for the same `j`.
+#[rustfmt::skip]
+const INV: [f64; 182] = [
+    1.4143646408839778, 1.4065934065934067, 1.3989071038251366,
+    1.391304347826087, 1.3837837837837839, 1.3763440860215055,
+    1.3689839572192513, 1.3617021276595744, 1.3544973544973544,
+    1.3473684210526315, 1.3403141361256545, 1.3333333333333333,
+    1.3264248704663213, 1.3195876288659794, 1.3128205128205128,
+    1.3061224489795917, 1.299492385786802, 1.292929292929293,
+    1.2864321608040201, 1.28, 1.2736318407960199,
+    1.2673267326732673, 1.2610837438423645, 1.2549019607843137,
+    1.248780487804878, 1.2427184466019416, 1.2367149758454106,
+    1.2307692307692308, 1.2248803827751196, 1.2190476190476192,
+    1.2132701421800949, 1.2075471698113207, 1.2018779342723005,
+    1.1962616822429906, 1.1906976744186046, 1.1851851851851851,
+    1.1797235023041475, 1.1743119266055047, 1.1689497716894977,
+    1.1636363636363636, 1.158371040723982, 1.1531531531531531,
+    1.147982062780269, 1.1428571428571428, 1.1377777777777778,
+    1.1327433628318584, 1.1277533039647578, 1.1228070175438596,
+    1.1179039301310043, 1.1130434782608696, 1.1082251082251082,
+    1.103448275862069, 1.0987124463519313, 1.0940170940170941,
+    1.0893617021276596, 1.0847457627118644, 1.080168776371308,
+    1.0756302521008403, 1.0711297071129706, 1.0666666666666667,
+    1.062240663900415, 1.0578512396694215, 1.0534979423868314,
+    1.0491803278688525, 1.0448979591836736, 1.0406504065040652,
+    1.0364372469635628, 1.032258064516129, 1.0281124497991967,
+    1.024, 1.0199203187250996, 1.0158730158730158,
+    1.0118577075098814, 1.0078740157480315, 1.003921568627451,
+    1.0, 0.9961089494163424, 0.9922480620155039,
+    0.9884169884169884, 0.9846153846153847, 0.9808429118773946,
+    0.9770992366412213, 0.973384030418251, 0.9696969696969697,
+    0.9660377358490566, 0.9624060150375939, 0.9588014981273408,
+    0.9552238805970149, 0.9516728624535316, 0.9481481481481482,
+    0.9446494464944649, 0.9411764705882353, 0.9377289377289377,
+    0.9343065693430657, 0.9309090909090909, 0.927536231884058,
+    0.924187725631769, 0.920863309352518, 0.9175627240143369,
+    0.9142857142857143, 0.9110320284697508, 0.9078014184397163,
+    0.9045936395759717, 0.9014084507042254, 0.8982456140350877,
+    0.8951048951048951, 0.89198606271777, 0.8888888888888888,
+    0.8858131487889274, 0.8827586206896552, 0.8797250859106529,
+    0.8767123287671232, 0.8737201365187713, 0.8707482993197279,
+    0.8677966101694915, 0.8648648648648649, 0.8619528619528619,
+    0.8590604026845637, 0.8561872909698997, 0.8533333333333334,
+    0.8504983388704319, 0.847682119205298, 0.8448844884488449,
+    0.8421052631578947, 0.839344262295082, 0.8366013071895425,
+    0.8338762214983714, 0.8311688311688312, 0.8284789644012945,
+    0.8258064516129032, 0.8231511254019293, 0.8205128205128205,
+    0.8178913738019169, 0.8152866242038217, 0.8126984126984127,
+    0.810126582278481, 0.807570977917981, 0.8050314465408805,
+    0.8025078369905956, 0.8, 0.7975077881619937,
+    0.7950310559006211, 0.7925696594427245, 0.7901234567901234,
+    0.7876923076923077, 0.7852760736196319, 0.7828746177370031,
+    0.7804878048780488, 0.7781155015197568, 0.7757575757575758,
+    0.7734138972809668, 0.7710843373493976, 0.7687687687687688,
+    0.7664670658682635, 0.764179104477612, 0.7619047619047619,
+    0.7596439169139466, 0.757396449704142, 0.7551622418879056,
+    0.7529411764705882, 0.750733137829912, 0.7485380116959064,
+    0.7463556851311953, 0.7441860465116279, 0.7420289855072464,
+    0.7398843930635838, 0.7377521613832853, 0.735632183908046,
+    0.7335243553008596, 0.7314285714285714, 0.7293447293447294,
+    0.7272727272727273, 0.7252124645892352, 0.7231638418079096,
+    0.7211267605633803, 0.7191011235955056, 0.7170868347338936,
+    0.7150837988826816, 0.713091922005571, 0.7111111111111111,
+    0.7091412742382271, 0.7071823204419889,
+];
+
+/// `1 / sqrt(j / 64)` for `j` in `64..=256`, covering `[1, 4]`.
+#[rustfmt::skip]
+#[allow(clippy::approx_constant)]
+const RSQRT: [f64; 193] = [
+    1.0, 0.9922778767136676, 0.9847319278346619,
+    0.9773555548504418, 0.9701425001453319, 0.9630868246861536,
+    0.9561828874675149, 0.9494253265550827, 0.9428090415820634,
+    0.9363291775690445, 0.9299811099505543, 0.9237604307034012,
+    0.9176629354822471, 0.9116846116771036, 0.9058216273156766,
+    0.9000703207408192, 0.8944271909999159, 0.8888888888888888,
+    0.8834522085987724, 0.8781140799175228, 0.8728715609439696,
+    0.8677218312746247, 0.8626621856275073, 0.8576900278702358,
+    0.8528028654224418, 0.847998304005088, 0.8432740427115678,
+    0.8386278693775346, 0.8340576562282991, 0.8295613557843402,
+    0.8251369970070347, 0.8207826816681233, 0.816496580927726,
+    0.8122769321068952, 0.8081220356417687, 0.8040302522073697,
+    0.8, 0.7960297521679913, 0.7921180343813394,
+    0.7882634225314346, 0.7844645405527362, 0.7807200583588265,
+    0.7770286898858113, 0.7733891912365308, 0.769800358919501,
+    0.7662610281769211, 0.7627700713964739, 0.7593263966019992,
+    0.7559289460184545, 0.7525766947068778, 0.7492686492653552,
+    0.746003846592251, 0.7427813527082074, 0.7396002616336388,
+    0.7364596943186587, 0.7333587976225691, 0.7302967433402214,
+    0.7272727272727273, 0.7242859683401482, 0.7213357077339458,
+    0.7184212081070996, 0.7155417527999327, 0.7126966450997984,
+    0.709885207532891, 0.7071067811865476, 0.7043607250604991,
+    0.7016464154456233, 0.6989632453288304, 0.6963106238227914,
+    0.693687975619296, 0.6910947404650881, 0.6885303726590963,
+    0.6859943405700354, 0.6834861261734088, 0.6810052246069989,
+    0.6785511437439767, 0.6761234037828132, 0.6737215368532151,
+    0.6713450866373513, 0.6689936080056726, 0.6666666666666666,
+    0.6643638388299198, 0.6620847108818944, 0.659828879073858,
+    0.6575959492214292, 0.6553855364152323, 0.6531972647421809,
+    0.6510307670169275, 0.6488856845230502, 0.6467616667635546,
+    0.6446583712203042, 0.6425754631219991, 0.6405126152203485,
+    0.6384695075740977, 0.6364458273405841, 0.6344412685745153,
+    0.6324555320336759, 0.6304883249912805, 0.6285393610547089,
+    0.6266083599903659, 0.6246950475544243, 0.6227991553292184,
+    0.6209204205650662, 0.6190585860273095, 0.6172133998483676,
+    0.6153846153846154, 0.6135719910778964, 0.611775290321498,
+    0.6099942813304187, 0.6082287370157644, 0.6064784348631227,
+    0.6047431568147635, 0.6030226891555273, 0.601316822402263,
+    0.5996253511966891, 0.5979480742015487, 0.5962847939999439,
+    0.5946353169977331, 0.5929994533288809, 0.5913770167636583,
+    0.5897678246195885, 0.5881716976750462, 0.5865884600854132,
+    0.5850179393017045, 0.5834599659915782, 0.5819143739626463,
+    0.5803810000880093, 0.5788596842339373, 0.5773502691896257,
+    0.5758526005989556, 0.5743665268941905, 0.5728918992315463,
+    0.5714285714285714, 0.5699763999032772, 0.5685352436149611,
+    0.5671049640066688, 0.565685424949238, 0.5642764926868786,
+    0.5628780357842335, 0.5614899250748772, 0.5601120336112039,
+    0.5587442366156625, 0.5573864114332941, 0.5560384374855327,
+    0.5547001962252291, 0.5533715710928597, 0.5520524474738834,
+    0.5507427126572114, 0.5494422557947561, 0.5481509678620253,
+    0.5468687416197306, 0.5455954715763788, 0.5443310539518174,
+    0.5430753866417045, 0.5418283691828771, 0.5405899027195887,
+    0.5393598899705937, 0.53813823519705, 0.5369248441712194,
+    0.53571962414594, 0.5345224838248488, 0.5333333333333333,
+    0.5321520841901914, 0.5309786492799776, 0.5298129428260175,
+    0.5286548803640718, 0.5275043787166296, 0.5263613559678152,
+    0.5252257314388902, 0.5240974256643347, 0.5229763603684907,
+    0.5218624584427538, 0.5207556439232954, 0.5196558419693047,
+    0.5185629788417315, 0.5174769818825207, 0.5163977794943223,
+    0.5153253011206647, 0.51425947722658, 0.5132002392796673,
+    0.5121475197315839, 0.5111012519999519, 0.5100613704506707,
+    0.5090278103806222, 0.508000508000762, 0.5069794004195823,
+    0.5059644256269407, 0.5049555224782425, 0.5039526306789697,
+    0.5029556907695452, 0.5019646441105269, 0.5009794328681196,
+    0.5,
+];
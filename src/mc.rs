@@ -0,0 +1,115 @@
+//! Random sources replicating Minecraft: Java Edition's world generation,
+//! using Mojang's names for them.
+
+use crate::math;
+use md5::{Digest, Md5};
+
+mod legacy;
+pub use legacy::*;
+
+mod xoroshiro;
+pub use xoroshiro::*;
+
+/// `RandomSupport.GOLDEN_RATIO_64`.
+pub const GOLDEN_RATIO_64: i64 = -7046029254386353131;
+
+/// `RandomSupport.SILVER_RATIO_64`.
+pub const SILVER_RATIO_64: i64 = 7640891576956012809;
+
+/// A 128-bit seed, like `RandomSupport.Seed128bit`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct Seed128bit {
+    pub seed_lo: i64,
+    pub seed_hi: i64,
+}
+
+impl Seed128bit {
+    #[inline]
+    #[must_use]
+    pub const fn new(seed_lo: i64, seed_hi: i64) -> Seed128bit {
+        Seed128bit { seed_lo, seed_hi }
+    }
+
+    /// Expand a world seed to 128 bits without mixing, like
+    /// `RandomSupport.upgradeSeedTo128bitUnmixed`.
+    #[inline]
+    #[must_use]
+    pub const fn upgrade_unmixed(seed: i64) -> Seed128bit {
+        let seed_lo = seed ^ SILVER_RATIO_64;
+        let seed_hi = seed_lo.wrapping_add(GOLDEN_RATIO_64);
+
+        Seed128bit::new(seed_lo, seed_hi)
+    }
+
+    /// Expand a world seed to 128 bits, like
+    /// `RandomSupport.upgradeSeedTo128bit`.
+    #[inline]
+    #[must_use]
+    pub const fn upgrade(seed: i64) -> Seed128bit {
+        Seed128bit::upgrade_unmixed(seed).mixed()
+    }
+
+    /// The big-endian halves of the MD5 digest of `name`, like
+    /// `RandomSupport.seedFromHashOf`.
+    #[must_use]
+    pub fn from_hash_of(name: &str) -> Seed128bit {
+        let digest: [u8; 16] = Md5::digest(name.as_bytes()).into();
+        let [seed_lo, seed_hi] = bytemuck::cast::<_, [[u8; 8]; 2]>(digest).map(i64::from_be_bytes);
+
+        Seed128bit::new(seed_lo, seed_hi)
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn xor(self, other: Seed128bit) -> Seed128bit {
+        Seed128bit::new(self.seed_lo ^ other.seed_lo, self.seed_hi ^ other.seed_hi)
+    }
+
+    /// Apply [`mix_stafford13`] to both halves.
+    #[inline]
+    #[must_use]
+    pub const fn mixed(self) -> Seed128bit {
+        Seed128bit::new(mix_stafford13(self.seed_lo), mix_stafford13(self.seed_hi))
+    }
+}
+
+/// Stafford's "Mix13" variant of the SplitMix64 finalizer, like
+/// `RandomSupport.mixStafford13`.
+#[inline]
+#[must_use]
+pub const fn mix_stafford13(mut seed: i64) -> i64 {
+    seed = (seed ^ (seed as u64 >> 30) as i64).wrapping_mul(-4658895280553007687);
+    seed = (seed ^ (seed as u64 >> 27) as i64).wrapping_mul(-7723592293110705685);
+
+    seed ^ (seed as u64 >> 31) as i64
+}
+
+/// Hash block coordinates into a seed, like `Mth.getSeed`.
+#[inline]
+#[must_use]
+pub const fn position_seed(x: i32, y: i32, z: i32) -> i64 {
+    let mut seed = (x.wrapping_mul(3129871) as i64) ^ (z as i64).wrapping_mul(116129781) ^ y as i64;
+    seed = seed
+        .wrapping_mul(seed)
+        .wrapping_mul(42317861)
+        .wrapping_add(seed.wrapping_mul(11));
+
+    seed >> 16
+}
+
+/// Generate a pair of gaussians with the Marsaglia polar method.
+#[inline]
+fn polar_gaussian(mut next_f64: impl FnMut() -> f64) -> (f64, f64) {
+    loop {
+        let v1 = 2.0 * next_f64() - 1.0;
+        let v2 = 2.0 * next_f64() - 1.0;
+        let s = v1 * v1 + v2 * v2;
+
+        if s < 1.0 && s != 0.0 {
+            let multiplier = math::sqrt(-2.0 * math::ln(s) / s);
+
+            break (v1 * multiplier, v2 * multiplier);
+        }
+    }
+}
@@ -0,0 +1,328 @@
+use super::{polar_gaussian, position_seed};
+use crate::consts;
+use core::ops::Range;
+
+/// Implement the methods Minecraft's `BitRandomSource` derives from
+/// `next(bits)`, taking `&self` or, given `mut`, `&mut self`.
+macro_rules! impl_bit_random_source {
+    ($ty:ty $(, $mut:tt)?) => {
+        impl $ty {
+            #[inline]
+            #[must_use]
+            pub fn next_i32(&$($mut)? self) -> i32 {
+                self.next_bits(32)
+            }
+
+            #[inline]
+            #[must_use]
+            pub fn next_i32_bounded(&$($mut)? self, bound: i32) -> i32 {
+                assert!(bound > 0, "bound must be positive");
+
+                if bound & (bound - 1) == 0 {
+                    return ((bound as i64).wrapping_mul(self.next_bits(31) as i64) >> 31) as i32;
+                }
+
+                loop {
+                    let bits = self.next_bits(31);
+                    let rem = bits % bound;
+
+                    if bits.wrapping_sub(rem).wrapping_add(bound - 1) >= 0 {
+                        break rem;
+                    }
+                }
+            }
+
+            /// Generate an `i32` in `range`, like `nextInt(origin, bound)`.
+            ///
+            /// Ranges wider than `i32::MAX`, where Java's `nextInt(bound)` would
+            /// throw, draw whole [`Self::next_i32`]s until one is in range.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `range` is empty.
+            #[inline]
+            #[must_use]
+            pub fn next_i32_ranged(&$($mut)? self, range: Range<i32>) -> i32 {
+                assert!(range.start < range.end, "bound must be greater than origin");
+
+                let n = range.end.wrapping_sub(range.start);
+
+                if n > 0 {
+                    range.start.wrapping_add(self.next_i32_bounded(n))
+                } else {
+                    loop {
+                        let r = self.next_i32();
+
+                        if range.contains(&r) {
+                            break r;
+                        }
+                    }
+                }
+            }
+
+            /// Generate an `i32` in `[min, max]`, like
+            /// `nextIntBetweenInclusive`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `min > max`, or the range is wider than
+            /// `i32::MAX`, where Java's `nextInt(bound)` throws.
+            #[inline]
+            #[must_use]
+            pub fn next_i32_between_inclusive(&$($mut)? self, min: i32, max: i32) -> i32 {
+                self.next_i32_bounded(max.wrapping_sub(min).wrapping_add(1))
+                    .wrapping_add(min)
+            }
+
+            #[inline]
+            #[must_use]
+            pub fn next_i64(&$($mut)? self) -> i64 {
+                let high = (self.next_bits(32) as i64) << 32;
+
+                high.wrapping_add(self.next_bits(32) as i64)
+            }
+
+            #[inline]
+            #[must_use]
+            pub fn next_bool(&$($mut)? self) -> bool {
+                self.next_bits(1) != 0
+            }
+
+            #[inline]
+            #[must_use]
+            pub fn next_f32(&$($mut)? self) -> f32 {
+                self.next_bits(24) as f32 * consts::FLOAT_UNIT
+            }
+
+            #[inline]
+            #[must_use]
+            pub fn next_f64(&$($mut)? self) -> f64 {
+                let high = (self.next_bits(26) as i64) << 27;
+                let low = self.next_bits(27) as i64;
+
+                (high + low) as f64 * consts::DOUBLE_UNIT
+            }
+
+            /// Generate an `f64` in `(mode - deviation, mode + deviation)`
+            /// with a triangular distribution, like `triangle`.
+            #[inline]
+            #[must_use]
+            pub fn triangle(&$($mut)? self, mode: f64, deviation: f64) -> f64 {
+                mode + deviation * (self.next_f64() - self.next_f64())
+            }
+
+            /// Advance the generator by `count` ints, like `consumeCount`.
+            #[inline]
+            pub fn consume_count(&$($mut)? self, count: u32) {
+                for _ in 0..count {
+                    let _ = self.next_i32();
+                }
+            }
+
+            /// Create a positional factory seeded with the next long, like
+            /// `forkPositional()`.
+            #[inline]
+            #[must_use]
+            pub fn fork_positional(&$($mut)? self) -> LegacyPositionalRandomFactory {
+                LegacyPositionalRandomFactory::new(self.next_i64())
+            }
+        }
+    };
+}
+
+/// Random source that replicates the behavior of Minecraft's
+/// `LegacyRandomSource`, the `java.util.Random` generator.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct LegacyRandomSource {
+    seed: i64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    next_gaussian: Option<f64>,
+}
+
+impl LegacyRandomSource {
+    #[inline]
+    #[must_use]
+    pub const fn with_seed(seed: i64) -> LegacyRandomSource {
+        LegacyRandomSource {
+            seed: (seed ^ consts::MULTIPLIER) & consts::MASK,
+            next_gaussian: None,
+        }
+    }
+
+    /// Reseed the generator, discarding the cached gaussian.
+    #[inline]
+    pub fn set_seed(&mut self, seed: i64) {
+        *self = LegacyRandomSource::with_seed(seed);
+    }
+
+    /// Create a new source seeded with the next long, like `fork()`.
+    #[inline]
+    #[must_use]
+    pub fn fork(&mut self) -> LegacyRandomSource {
+        LegacyRandomSource::with_seed(self.next_i64())
+    }
+
+    /// Generate the upper `bits` bits of the next state, like `next(bits)`.
+    #[inline]
+    #[must_use]
+    pub fn next_bits(&mut self, bits: u8) -> i32 {
+        self.seed = next_seed(self.seed);
+
+        (self.seed >> (48 - bits)) as i32
+    }
+
+    /// Generate a gaussian with the Marsaglia polar method, like
+    /// `MarsagliaPolarGaussian`.
+    #[inline]
+    #[must_use]
+    pub fn next_gaussian(&mut self) -> f64 {
+        if let Some(next_gaussian) = self.next_gaussian.take() {
+            return next_gaussian;
+        }
+
+        let (next, next_gaussian) = polar_gaussian(|| self.next_f64());
+        self.next_gaussian = Some(next_gaussian);
+
+        next
+    }
+}
+
+impl_bit_random_source!(LegacyRandomSource, mut);
+
+/// Random source that replicates the behavior of Minecraft's
+/// `ThreadSafeLegacyRandomSource`, which advances its seed with
+/// compare-and-swap so it can be shared between threads.
+///
+/// Unlike [`LegacyRandomSource`], reseeding keeps the cached gaussian, as it
+/// does in Minecraft.
+#[derive(Debug)]
+#[cfg(target_has_atomic = "64")]
+pub struct ThreadSafeLegacyRandomSource {
+    seed: core::sync::atomic::AtomicI64,
+    /// The bits of the cached gaussian, or [`NO_GAUSSIAN`].
+    next_gaussian: core::sync::atomic::AtomicU64,
+}
+
+/// A NaN that the polar method never produces.
+#[cfg(target_has_atomic = "64")]
+const NO_GAUSSIAN: u64 = u64::MAX;
+
+#[cfg(target_has_atomic = "64")]
+impl ThreadSafeLegacyRandomSource {
+    #[inline]
+    #[must_use]
+    pub const fn with_seed(seed: i64) -> ThreadSafeLegacyRandomSource {
+        use core::sync::atomic::{AtomicI64, AtomicU64};
+
+        ThreadSafeLegacyRandomSource {
+            seed: AtomicI64::new((seed ^ consts::MULTIPLIER) & consts::MASK),
+            next_gaussian: AtomicU64::new(NO_GAUSSIAN),
+        }
+    }
+
+    #[inline]
+    pub fn set_seed(&self, seed: i64) {
+        use core::sync::atomic::Ordering;
+
+        self.seed.store(
+            (seed ^ consts::MULTIPLIER) & consts::MASK,
+            Ordering::Relaxed,
+        );
+    }
+
+    /// Create a new source seeded with the next long, like `fork()`.
+    #[inline]
+    #[must_use]
+    pub fn fork(&self) -> ThreadSafeLegacyRandomSource {
+        ThreadSafeLegacyRandomSource::with_seed(self.next_i64())
+    }
+
+    /// Generate the upper `bits` bits of the next state, like `next(bits)`.
+    #[inline]
+    #[must_use]
+    pub fn next_bits(&self, bits: u8) -> i32 {
+        use core::sync::atomic::Ordering;
+
+        let mut prev = self.seed.load(Ordering::Relaxed);
+
+        let next = loop {
+            let next = next_seed(prev);
+
+            match self
+                .seed
+                .compare_exchange_weak(prev, next, Ordering::Relaxed, Ordering::Relaxed)
+            {
+                Ok(_) => break next,
+                Err(next_prev) => prev = next_prev,
+            }
+        };
+
+        (next as u64 >> (48 - bits)) as i32
+    }
+
+    /// Generate a gaussian with the Marsaglia polar method, like
+    /// `MarsagliaPolarGaussian`.
+    #[inline]
+    #[must_use]
+    pub fn next_gaussian(&self) -> f64 {
+        use core::sync::atomic::Ordering;
+
+        let cached = self.next_gaussian.swap(NO_GAUSSIAN, Ordering::Relaxed);
+
+        if cached != NO_GAUSSIAN {
+            return f64::from_bits(cached);
+        }
+
+        let (next, next_gaussian) = polar_gaussian(|| self.next_f64());
+        self.next_gaussian
+            .store(next_gaussian.to_bits(), Ordering::Relaxed);
+
+        next
+    }
+}
+
+#[cfg(target_has_atomic = "64")]
+impl_bit_random_source!(ThreadSafeLegacyRandomSource);
+
+/// Factory for legacy random sources derived from a position or a name, like
+/// Minecraft's `LegacyRandomSource.LegacyPositionalRandomFactory`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct LegacyPositionalRandomFactory {
+    seed: i64,
+}
+
+impl LegacyPositionalRandomFactory {
+    #[inline]
+    #[must_use]
+    pub const fn new(seed: i64) -> LegacyPositionalRandomFactory {
+        LegacyPositionalRandomFactory { seed }
+    }
+
+    /// Create the random source for a block position, like `at(x, y, z)`.
+    #[inline]
+    #[must_use]
+    pub const fn at(&self, x: i32, y: i32, z: i32) -> LegacyRandomSource {
+        LegacyRandomSource::with_seed(position_seed(x, y, z) ^ self.seed)
+    }
+
+    /// Create the random source for a name, like `fromHashOf(String)`, which
+    /// seeds it with Java's `String.hashCode()` of the name.
+    #[inline]
+    #[must_use]
+    pub fn from_hash_of(&self, name: &str) -> LegacyRandomSource {
+        let hash = name.encode_utf16().fold(0i32, |hash, unit| {
+            hash.wrapping_mul(31).wrapping_add(unit as i32)
+        });
+
+        LegacyRandomSource::with_seed(hash as i64 ^ self.seed)
+    }
+}
+
+#[inline]
+const fn next_seed(seed: i64) -> i64 {
+    seed.wrapping_mul(consts::MULTIPLIER)
+        .wrapping_add(consts::ADDEND)
+        & consts::MASK
+}
@@ -0,0 +1,255 @@
+use super::{polar_gaussian, position_seed, Seed128bit, GOLDEN_RATIO_64, SILVER_RATIO_64};
+use core::ops::Range;
+
+/// Random source that replicates the behavior of Minecraft's
+/// `XoroshiroRandomSource`, a xoroshiro128++ generator.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct XoroshiroRandomSource {
+    seed_lo: i64,
+    seed_hi: i64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    next_gaussian: Option<f64>,
+}
+
+impl XoroshiroRandomSource {
+    /// Create a random source from a world seed, expanded with
+    /// [`Seed128bit::upgrade`].
+    #[inline]
+    #[must_use]
+    pub const fn with_seed(seed: i64) -> XoroshiroRandomSource {
+        XoroshiroRandomSource::with_seed128(Seed128bit::upgrade(seed))
+    }
+
+    /// Create a random source with the specified 128-bit state.
+    ///
+    /// Like Minecraft, an all-zero state is replaced with a fixed nonzero one.
+    #[inline]
+    #[must_use]
+    pub const fn with_seed128(seed: Seed128bit) -> XoroshiroRandomSource {
+        let Seed128bit {
+            mut seed_lo,
+            mut seed_hi,
+        } = seed;
+
+        if seed_lo | seed_hi == 0 {
+            seed_lo = GOLDEN_RATIO_64;
+            seed_hi = SILVER_RATIO_64;
+        }
+
+        XoroshiroRandomSource {
+            seed_lo,
+            seed_hi,
+            next_gaussian: None,
+        }
+    }
+
+    /// Reseed from a world seed, discarding the cached gaussian.
+    #[inline]
+    pub fn set_seed(&mut self, seed: i64) {
+        *self = XoroshiroRandomSource::with_seed(seed);
+    }
+
+    /// Create a new source seeded with the next two longs, like `fork()`.
+    #[inline]
+    #[must_use]
+    pub fn fork(&mut self) -> XoroshiroRandomSource {
+        XoroshiroRandomSource::with_seed128(self.next_seed128())
+    }
+
+    /// Create a positional factory seeded with the next two longs, like
+    /// `forkPositional()`.
+    #[inline]
+    #[must_use]
+    pub fn fork_positional(&mut self) -> XoroshiroPositionalRandomFactory {
+        XoroshiroPositionalRandomFactory {
+            seed: self.next_seed128(),
+        }
+    }
+
+    #[inline]
+    fn next_seed128(&mut self) -> Seed128bit {
+        let seed_lo = self.next_i64();
+        let seed_hi = self.next_i64();
+
+        Seed128bit::new(seed_lo, seed_hi)
+    }
+}
+
+impl XoroshiroRandomSource {
+    #[inline]
+    #[must_use]
+    pub fn next_i64(&mut self) -> i64 {
+        let lo = self.seed_lo;
+        let mut hi = self.seed_hi;
+
+        let next = lo.wrapping_add(hi).rotate_left(17).wrapping_add(lo);
+
+        hi ^= lo;
+        self.seed_lo = lo.rotate_left(49) ^ hi ^ (hi << 21);
+        self.seed_hi = hi.rotate_left(28);
+
+        next
+    }
+
+    #[inline]
+    #[must_use]
+    fn next_bits(&mut self, bits: u8) -> i64 {
+        (self.next_i64() as u64 >> (64 - bits)) as i64
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn next_i32(&mut self) -> i32 {
+        self.next_i64() as i32
+    }
+
+    /// Generate an `i32` in `[0, bound)` with Lemire's multiply-and-reject
+    /// method.
+    #[inline]
+    #[must_use]
+    pub fn next_i32_bounded(&mut self, bound: i32) -> i32 {
+        assert!(bound > 0, "bound must be positive");
+
+        let bound = bound as u64;
+
+        let mut product = self.next_i32() as u32 as u64 * bound;
+        let mut low = product & 0xffff_ffff;
+
+        if low < bound {
+            let threshold = (bound.wrapping_neg() as u32 % bound as u32) as u64;
+
+            while low < threshold {
+                product = self.next_i32() as u32 as u64 * bound;
+                low = product & 0xffff_ffff;
+            }
+        }
+
+        (product >> 32) as i32
+    }
+
+    /// Generate an `i32` in `range`, like `nextInt(origin, bound)`.
+    ///
+    /// Ranges wider than `i32::MAX`, where Java's `nextInt(bound)` would
+    /// throw, draw whole [`Self::next_i32`]s until one is in range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is empty.
+    #[inline]
+    #[must_use]
+    pub fn next_i32_ranged(&mut self, range: Range<i32>) -> i32 {
+        assert!(range.start < range.end, "bound must be greater than origin");
+
+        let n = range.end.wrapping_sub(range.start);
+
+        if n > 0 {
+            range.start.wrapping_add(self.next_i32_bounded(n))
+        } else {
+            loop {
+                let r = self.next_i32();
+
+                if range.contains(&r) {
+                    break r;
+                }
+            }
+        }
+    }
+
+    /// Generate an `i32` in `[min, max]`, like `nextIntBetweenInclusive`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min > max`, or the range is wider than `i32::MAX`, where
+    /// Java's `nextInt(bound)` throws.
+    #[inline]
+    #[must_use]
+    pub fn next_i32_between_inclusive(&mut self, min: i32, max: i32) -> i32 {
+        self.next_i32_bounded(max.wrapping_sub(min).wrapping_add(1))
+            .wrapping_add(min)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn next_bool(&mut self) -> bool {
+        self.next_i64() & 1 != 0
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn next_f32(&mut self) -> f32 {
+        self.next_bits(24) as f32 * (1.0 / (1 << 24) as f32)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn next_f64(&mut self) -> f64 {
+        self.next_bits(53) as f64 * (1.0 / (1_i64 << 53) as f64)
+    }
+
+    /// Generate a gaussian with the Marsaglia polar method, like
+    /// `MarsagliaPolarGaussian`.
+    #[inline]
+    #[must_use]
+    pub fn next_gaussian(&mut self) -> f64 {
+        if let Some(next_gaussian) = self.next_gaussian.take() {
+            return next_gaussian;
+        }
+
+        let (next, next_gaussian) = polar_gaussian(|| self.next_f64());
+        self.next_gaussian = Some(next_gaussian);
+
+        next
+    }
+
+    /// Generate an `f64` in `(mode - deviation, mode + deviation)` with a
+    /// triangular distribution, like `triangle`.
+    #[inline]
+    #[must_use]
+    pub fn triangle(&mut self, mode: f64, deviation: f64) -> f64 {
+        mode + deviation * (self.next_f64() - self.next_f64())
+    }
+
+    /// Advance the generator by `count` longs, like `consumeCount`.
+    #[inline]
+    pub fn consume_count(&mut self, count: u32) {
+        for _ in 0..count {
+            let _ = self.next_i64();
+        }
+    }
+}
+
+/// Factory for random sources derived from a position or a name, like
+/// Minecraft's `XoroshiroRandomSource.XoroshiroPositionalRandomFactory`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct XoroshiroPositionalRandomFactory {
+    seed: Seed128bit,
+}
+
+impl XoroshiroPositionalRandomFactory {
+    #[inline]
+    #[must_use]
+    pub const fn new(seed: Seed128bit) -> XoroshiroPositionalRandomFactory {
+        XoroshiroPositionalRandomFactory { seed }
+    }
+
+    /// Create the random source for a block position, like `at(x, y, z)`.
+    #[inline]
+    #[must_use]
+    pub const fn at(&self, x: i32, y: i32, z: i32) -> XoroshiroRandomSource {
+        XoroshiroRandomSource::with_seed128(Seed128bit::new(
+            position_seed(x, y, z) ^ self.seed.seed_lo,
+            self.seed.seed_hi,
+        ))
+    }
+
+    /// Create the random source for a name, like `fromHashOf(String)`.
+    ///
+    /// For a resource location, pass its `namespace:path` form.
+    #[inline]
+    #[must_use]
+    pub fn from_hash_of(&self, name: &str) -> XoroshiroRandomSource {
+        XoroshiroRandomSource::with_seed128(Seed128bit::from_hash_of(name).xor(self.seed))
+    }
+}
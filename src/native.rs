@@ -0,0 +1,47 @@
+use crate::secure::secure_next;
+
+/// Random number generator backed by the operating system's CSPRNG, in the
+/// spirit of Java's `NativePRNG` `SecureRandom`.
+///
+/// It has the same methods as [`JavaRng`](crate::JavaRng), and derives its
+/// values from random bytes the way `java.security.SecureRandom` does, but
+/// its output is not reproducible.
+#[derive(Debug, Clone, Default)]
+pub struct NativeRng {
+    pub(crate) next_gaussian: Option<f64>,
+}
+
+impl NativeRng {
+    /// Create a random number generator backed by the operating system.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> NativeRng {
+        NativeRng {
+            next_gaussian: None,
+        }
+    }
+}
+
+impl NativeRng {
+    #[inline]
+    #[must_use]
+    pub(crate) fn next(&mut self, bits: u8) -> i32 {
+        secure_next(bits, |bytes| self.next_bytes(bytes))
+    }
+}
+
+impl_random!(NativeRng);
+
+impl NativeRng {
+    /// Fill `bytes` with random bytes from the operating system.
+    #[inline]
+    pub fn try_next_bytes(&mut self, bytes: &mut [u8]) -> Result<(), getrandom::Error> {
+        getrandom::getrandom(bytes)
+    }
+
+    #[inline]
+    pub fn next_bytes(&mut self, bytes: &mut [u8]) {
+        self.try_next_bytes(bytes)
+            .expect("failed to get random bytes from the operating system")
+    }
+}
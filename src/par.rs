@@ -0,0 +1,197 @@
+//! Parallel value streams over [`rayon`].
+//!
+//! Each value of a stream takes a fixed number of steps of the generator, so
+//! a stream can be split anywhere by jumping ahead, and every thread
+//! generates its part starting from the exact state the sequential stream
+//! would have reached.
+
+use core::iter::FusedIterator;
+
+use rayon::iter::{
+    plumbing::{bridge, Consumer, Producer, ProducerCallback, UnindexedConsumer},
+    IndexedParallelIterator, ParallelIterator,
+};
+
+use crate::JavaRng;
+
+impl JavaRng {
+    #[inline]
+    fn par_stream<T>(&mut self, len: usize, steps: u64, f: fn(&mut JavaRng) -> T) -> ParStream<T> {
+        let state = self.state();
+
+        self.skip(len as u64 * steps);
+
+        ParStream {
+            state,
+            len,
+            steps,
+            f,
+        }
+    }
+
+    /// Generate the same values as [`JavaRng::i32_stream`], in parallel.
+    ///
+    /// The random number generator is advanced past the whole stream
+    /// immediately, as if it had been consumed.
+    #[inline]
+    pub fn par_i32_stream(&mut self, len: usize) -> ParStream<i32> {
+        self.par_stream(len, 1, JavaRng::next_i32)
+    }
+
+    /// Generate the same values as [`JavaRng::i64_stream`], in parallel.
+    ///
+    /// The random number generator is advanced past the whole stream
+    /// immediately, as if it had been consumed.
+    #[inline]
+    pub fn par_i64_stream(&mut self, len: usize) -> ParStream<i64> {
+        self.par_stream(len, 2, JavaRng::next_i64)
+    }
+
+    /// Generate the same values as `len` calls to [`JavaRng::next_bool`], in
+    /// parallel.
+    ///
+    /// The random number generator is advanced past the whole stream
+    /// immediately, as if it had been consumed.
+    #[inline]
+    pub fn par_bool_stream(&mut self, len: usize) -> ParStream<bool> {
+        self.par_stream(len, 1, JavaRng::next_bool)
+    }
+
+    /// Generate the same values as `len` calls to [`JavaRng::next_f32`], in
+    /// parallel.
+    ///
+    /// The random number generator is advanced past the whole stream
+    /// immediately, as if it had been consumed.
+    #[inline]
+    pub fn par_f32_stream(&mut self, len: usize) -> ParStream<f32> {
+        self.par_stream(len, 1, JavaRng::next_f32)
+    }
+
+    /// Generate the same values as [`JavaRng::f64_stream`], in parallel.
+    ///
+    /// The random number generator is advanced past the whole stream
+    /// immediately, as if it had been consumed.
+    #[inline]
+    pub fn par_f64_stream(&mut self, len: usize) -> ParStream<f64> {
+        self.par_stream(len, 2, JavaRng::next_f64)
+    }
+}
+
+/// A parallel stream of values, each taking the same number of steps of a
+/// [`JavaRng`].
+#[derive(Debug, Clone, Copy)]
+pub struct ParStream<T> {
+    state: i64,
+    len: usize,
+    steps: u64,
+    f: fn(&mut JavaRng) -> T,
+}
+
+impl<T: Send> ParallelIterator for ParStream<T> {
+    type Item = T;
+
+    #[inline]
+    fn drive_unindexed<C: UnindexedConsumer<T>>(self, consumer: C) -> C::Result {
+        bridge(self, consumer)
+    }
+
+    #[inline]
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.len)
+    }
+}
+
+impl<T: Send> IndexedParallelIterator for ParStream<T> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    fn drive<C: Consumer<T>>(self, consumer: C) -> C::Result {
+        bridge(self, consumer)
+    }
+
+    #[inline]
+    fn with_producer<CB: ProducerCallback<T>>(self, callback: CB) -> CB::Output {
+        callback.callback(self)
+    }
+}
+
+impl<T: Send> Producer for ParStream<T> {
+    type Item = T;
+    type IntoIter = ParStreamIter<T>;
+
+    #[inline]
+    fn into_iter(self) -> ParStreamIter<T> {
+        ParStreamIter {
+            front: JavaRng::from_state(self.state),
+            back: JavaRng::from_state(self.state).skipped(self.len as u64 * self.steps),
+            len: self.len,
+            steps: self.steps,
+            f: self.f,
+        }
+    }
+
+    #[inline]
+    fn split_at(self, index: usize) -> (ParStream<T>, ParStream<T>) {
+        let right = JavaRng::from_state(self.state).skipped(index as u64 * self.steps);
+
+        (
+            ParStream { len: index, ..self },
+            ParStream {
+                state: right.state(),
+                len: self.len - index,
+                ..self
+            },
+        )
+    }
+}
+
+/// The sequential part of a [`ParStream`] one thread generates.
+#[derive(Debug, Clone)]
+pub struct ParStreamIter<T> {
+    front: JavaRng,
+    back: JavaRng,
+    len: usize,
+    steps: u64,
+    f: fn(&mut JavaRng) -> T,
+}
+
+impl<T> Iterator for ParStreamIter<T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+
+        self.len -= 1;
+
+        Some((self.f)(&mut self.front))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for ParStreamIter<T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+
+        self.len -= 1;
+        self.back.step_back(self.steps);
+
+        Some((self.f)(&mut self.back.clone()))
+    }
+}
+
+impl<T> ExactSizeIterator for ParStreamIter<T> {}
+
+impl<T> FusedIterator for ParStreamIter<T> {}
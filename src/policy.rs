@@ -0,0 +1,129 @@
+use crate::{math, strictmath};
+
+/// The functions gaussians are computed with, chosen with a type parameter
+/// such as in [`JavaRng::next_gaussian_using`](crate::JavaRng).
+pub trait MathPolicy {
+    /// The natural logarithm of `x`.
+    fn ln(x: f64) -> f64;
+
+    /// The square root of `x`.
+    fn sqrt(x: f64) -> f64;
+
+    /// `e` raised to `x`.
+    fn exp(x: f64) -> f64;
+
+    /// The sine of `x`.
+    fn sin(x: f64) -> f64;
+
+    /// The cosine of `x`.
+    fn cos(x: f64) -> f64;
+}
+
+/// The fdlibm ports of [`strictmath`], which give bit-for-bit what Java's
+/// `StrictMath` does on every target, and so what `nextGaussian` does.
+///
+/// This is what `next_gaussian` uses.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Strict;
+
+impl MathPolicy for Strict {
+    #[inline]
+    fn ln(x: f64) -> f64 {
+        strictmath::log(x)
+    }
+
+    #[inline]
+    fn sqrt(x: f64) -> f64 {
+        strictmath::sqrt(x)
+    }
+
+    #[inline]
+    fn exp(x: f64) -> f64 {
+        strictmath::exp(x)
+    }
+
+    #[inline]
+    fn sin(x: f64) -> f64 {
+        strictmath::sin(x)
+    }
+
+    #[inline]
+    fn cos(x: f64) -> f64 {
+        strictmath::cos(x)
+    }
+}
+
+/// The platform's math library, from `std` or else `libm`, which is usually
+/// faster but only promises results within an ulp or so of Java's.
+///
+/// Without either, this is the same as [`Strict`]. The square root is
+/// correctly rounded everywhere, so only the transcendental functions can
+/// differ.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Fast;
+
+impl MathPolicy for Fast {
+    #[inline]
+    fn ln(x: f64) -> f64 {
+        math::ln(x)
+    }
+
+    #[inline]
+    fn sqrt(x: f64) -> f64 {
+        math::sqrt(x)
+    }
+
+    #[inline]
+    fn exp(x: f64) -> f64 {
+        math::exp(x)
+    }
+
+    #[inline]
+    fn sin(x: f64) -> f64 {
+        math::sin(x)
+    }
+
+    #[inline]
+    fn cos(x: f64) -> f64 {
+        math::cos(x)
+    }
+}
+
+/// Table-driven [`ln`](MathPolicy::ln) and [`sqrt`](MathPolicy::sqrt),
+/// avoiding division, for targets where floating point is done in software
+/// and the fdlibm ports are slow, with the rest as in [`Fast`].
+///
+/// The logarithm is within a few ulps and the square root within one, so
+/// gaussians differ from Java's in their last bits, but not by more. The
+/// tables take about 4.5KB.
+#[cfg(feature = "table-math")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Table;
+
+#[cfg(feature = "table-math")]
+impl MathPolicy for Table {
+    #[inline]
+    fn ln(x: f64) -> f64 {
+        math::table::ln(x)
+    }
+
+    #[inline]
+    fn sqrt(x: f64) -> f64 {
+        math::table::sqrt(x)
+    }
+
+    #[inline]
+    fn exp(x: f64) -> f64 {
+        math::exp(x)
+    }
+
+    #[inline]
+    fn sin(x: f64) -> f64 {
+        math::sin(x)
+    }
+
+    #[inline]
+    fn cos(x: f64) -> f64 {
+        math::cos(x)
+    }
+}
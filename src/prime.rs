@@ -0,0 +1,461 @@
+//! Probable prime generation replicating `java.math.BigInteger`.
+
+use num_bigint::{BigInt, BigUint, Sign};
+use std::{vec, vec::Vec};
+
+/// The certainty of `BigInteger.probablePrime`.
+pub(crate) const DEFAULT_PRIME_CERTAINTY: i32 = 100;
+
+/// Below this many bits, candidates are drawn whole rather than sieved.
+const SMALL_PRIME_THRESHOLD: u32 = 95;
+const PRIME_SEARCH_BIT_LENGTH_LIMIT: u32 = 500_000_000;
+
+const SMALL_PRIME_PRODUCT: u64 = 3 * 5 * 7 * 11 * 13 * 17 * 19 * 23 * 29 * 31 * 37 * 41;
+
+/// The draws prime generation makes from a random number generator.
+pub(crate) trait Source {
+    fn next_i32(&mut self) -> i32;
+
+    /// Like `new BigInteger(numBits, rnd)`.
+    fn random_bits(&mut self, num_bits: u32) -> BigUint;
+}
+
+/// Generate a prime of `bit_length` bits like
+/// `new BigInteger(bitLength, certainty, rnd)`.
+pub(crate) fn probable_prime<R: Source + ?Sized>(
+    rng: &mut R,
+    bit_length: u32,
+    certainty: i32,
+) -> BigUint {
+    assert!(bit_length >= 2, "bitLength < 2");
+
+    if bit_length < SMALL_PRIME_THRESHOLD {
+        small_prime(rng, bit_length, certainty)
+    } else {
+        large_prime(rng, bit_length, certainty)
+    }
+}
+
+/// Draw whole candidates from `nextInt()` until one is prime.
+fn small_prime<R: Source + ?Sized>(rng: &mut R, bit_length: u32, certainty: i32) -> BigUint {
+    let mag_len = bit_length.div_ceil(32) as usize;
+    let high_bit = 1_u32 << ((bit_length + 31) & 0x1f);
+    let high_mask = (high_bit << 1).wrapping_sub(1);
+
+    let mut temp = vec![0_u32; mag_len];
+
+    loop {
+        temp.iter_mut().for_each(|x| *x = rng.next_i32() as u32);
+
+        temp[0] = (temp[0] & high_mask) | high_bit;
+
+        if bit_length > 2 {
+            temp[mag_len - 1] |= 1;
+        }
+
+        let bytes: Vec<u8> = temp.iter().flat_map(|x| x.to_be_bytes()).collect();
+        let p = BigUint::from_bytes_be(&bytes);
+
+        // A cheap pre-test against the small primes.
+        if bit_length > 6 {
+            let r = (&p % SMALL_PRIME_PRODUCT)
+                .iter_u64_digits()
+                .next()
+                .unwrap_or(0);
+
+            if [3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41]
+                .iter()
+                .any(|&prime| r.is_multiple_of(prime))
+            {
+                continue;
+            }
+        }
+
+        // Every candidate of 2 or 3 bits is prime by now.
+        if bit_length < 4 || prime_to_certainty(&p, certainty, rng) {
+            break p;
+        }
+    }
+}
+
+/// Sieve runs of odd candidates after a random start until one is prime.
+fn large_prime<R: Source + ?Sized>(rng: &mut R, bit_length: u32, certainty: i32) -> BigUint {
+    assert!(
+        bit_length <= PRIME_SEARCH_BIT_LENGTH_LIMIT + 1,
+        "Prime search implementation restriction on bitLength"
+    );
+
+    let search_len = bit_length / 20 * 64;
+    let random_start = |rng: &mut R| {
+        let mut p = rng.random_bits(bit_length);
+        p.set_bit(bit_length as u64 - 1, true);
+        p
+    };
+
+    let mut p = random_start(rng);
+
+    loop {
+        p.set_bit(0, false);
+
+        let sieve = BitSieve::new(&p, search_len as usize);
+
+        match sieve.retrieve(&p, certainty, rng) {
+            Some(candidate) if candidate.bits() == bit_length as u64 => break candidate,
+            _ => {}
+        }
+
+        p += 2 * search_len;
+
+        if p.bits() != bit_length as u64 {
+            p = random_start(rng);
+        }
+    }
+}
+
+/// Like `BigInteger.primeToCertainty`.
+fn prime_to_certainty<R: Source + ?Sized>(p: &BigUint, certainty: i32, rng: &mut R) -> bool {
+    let n = (certainty.min(i32::MAX - 1) + 1) / 2;
+    let size_in_bits = p.bits();
+
+    if size_in_bits < 100 {
+        return passes_miller_rabin(p, n.min(50), rng);
+    }
+
+    let rounds = match size_in_bits {
+        100..256 => 27,
+        256..512 => 15,
+        512..768 => 8,
+        768..1024 => 4,
+        _ => 2,
+    };
+
+    passes_miller_rabin(p, n.min(rounds), rng) && passes_lucas_lehmer(p)
+}
+
+/// Like `BigInteger.passesMillerRabin`, drawing each base with
+/// `new BigInteger(bitLength, rnd)` until it is in `(1, p)`.
+fn passes_miller_rabin<R: Source + ?Sized>(p: &BigUint, iterations: i32, rng: &mut R) -> bool {
+    let one = BigUint::from(1_u32);
+    let two = BigUint::from(2_u32);
+
+    let p_minus_one = p - &one;
+    let a = p_minus_one.trailing_zeros().unwrap_or(0);
+    let m = &p_minus_one >> a;
+
+    for _ in 0..iterations {
+        let b = loop {
+            let b = rng.random_bits(p.bits() as u32);
+
+            if b > one && b < *p {
+                break b;
+            }
+        };
+
+        let mut j = 0;
+        let mut z = b.modpow(&m, p);
+
+        while !((j == 0 && z == one) || z == p_minus_one) {
+            if j > 0 && z == one {
+                return false;
+            }
+
+            j += 1;
+
+            if j == a {
+                return false;
+            }
+
+            z = z.modpow(&two, p);
+        }
+    }
+
+    true
+}
+
+/// Like `BigInteger.passesLucasLehmer`.
+fn passes_lucas_lehmer(p: &BigUint) -> bool {
+    let n = BigInt::from(p.clone());
+    let n_plus_one = &n + 1;
+
+    // 5, -7, 9, -11, ...
+    let mut d = 5_i32;
+
+    while jacobi_symbol(d, p) != -1 {
+        d = if d < 0 { d.abs() + 2 } else { -(d + 2) };
+    }
+
+    let u = lucas_lehmer_sequence(d, &n_plus_one, &n);
+
+    java_mod(&u, &n).sign() == Sign::NoSign
+}
+
+/// Like `BigInteger.jacobiSymbol`.
+fn jacobi_symbol(mut p: i32, n: &BigUint) -> i32 {
+    if p == 0 {
+        return 0;
+    }
+
+    let mut j = 1;
+    let mut u = n.iter_u32_digits().next().unwrap_or(0) as i32;
+
+    // Make p positive.
+    if p < 0 {
+        p = -p;
+
+        let n8 = u & 7;
+
+        if n8 == 3 || n8 == 7 {
+            j = -j;
+        }
+    }
+
+    // Get rid of factors of 2 in p.
+    while p & 3 == 0 {
+        p >>= 2;
+    }
+
+    if p & 1 == 0 {
+        p >>= 1;
+
+        if (u ^ (u >> 1)) & 2 != 0 {
+            j = -j;
+        }
+    }
+
+    if p == 1 {
+        return j;
+    }
+
+    // Apply quadratic reciprocity, and reduce u mod p.
+    if p & u & 2 != 0 {
+        j = -j;
+    }
+
+    u = (n % p as u32).iter_u32_digits().next().unwrap_or(0) as i32;
+
+    while u != 0 {
+        while u & 3 == 0 {
+            u >>= 2;
+        }
+
+        if u & 1 == 0 {
+            u >>= 1;
+
+            if (p ^ (p >> 1)) & 2 != 0 {
+                j = -j;
+            }
+        }
+
+        if u == 1 {
+            return j;
+        }
+
+        // Both are odd, so use quadratic reciprocity.
+        core::mem::swap(&mut u, &mut p);
+
+        if u & p & 2 != 0 {
+            j = -j;
+        }
+
+        u %= p;
+    }
+
+    0
+}
+
+/// Like `BigInteger.lucasLehmerSequence`.
+fn lucas_lehmer_sequence(z: i32, k: &BigInt, n: &BigInt) -> BigInt {
+    let d = BigInt::from(z);
+    let mut u = BigInt::from(1);
+    let mut v = BigInt::from(1);
+
+    // Halve an even value, or an odd one after subtracting `n`.
+    let halve = |x: BigInt| {
+        if x.bit(0) {
+            (x - n) >> 1
+        } else {
+            x >> 1
+        }
+    };
+
+    for i in (0..k.bits().saturating_sub(1)).rev() {
+        let u2 = java_mod(&(&u * &v), n);
+        let v2 = halve(java_mod(&(&v * &v + &d * (&u * &u)), n));
+
+        u = u2;
+        v = v2;
+
+        if k.bit(i) {
+            let u2 = halve(java_mod(&(&u + &v), n));
+            let v2 = halve(java_mod(&(&v + &d * &u), n));
+
+            u = u2;
+            v = v2;
+        }
+    }
+
+    u
+}
+
+/// `BigInteger.mod`, which is never negative.
+fn java_mod(x: &BigInt, n: &BigInt) -> BigInt {
+    let r = x % n;
+
+    if r.sign() == Sign::Minus {
+        r + n
+    } else {
+        r
+    }
+}
+
+/// Like `java.math.BitSieve`, where bit `i` stands for the odd number
+/// `base + 2 * i + 1`, and set bits are known composites.
+struct BitSieve {
+    bits: Vec<u64>,
+    length: usize,
+}
+
+impl BitSieve {
+    /// The sieve of small primes the other sieves are built from.
+    fn small() -> BitSieve {
+        let length = 150 * 64;
+        let mut sieve = BitSieve {
+            bits: vec![0; unit_index(length - 1) + 1],
+            length,
+        };
+
+        // Mark 1 as composite.
+        sieve.set(0);
+
+        let mut next_index = 1;
+        let mut next_prime = 3;
+
+        loop {
+            sieve.sieve_single(length, next_index + next_prime, next_prime);
+
+            match sieve.sieve_search(length, next_index + 1) {
+                Some(index) => next_index = index,
+                None => break,
+            }
+
+            next_prime = 2 * next_index + 1;
+
+            if next_prime >= length {
+                break;
+            }
+        }
+
+        sieve
+    }
+
+    /// Sieve the `search_len` odd numbers after `base`.
+    fn new(base: &BigUint, search_len: usize) -> BitSieve {
+        let small = BitSieve::small();
+        let mut sieve = BitSieve {
+            bits: vec![0; unit_index(search_len - 1) + 1],
+            length: search_len,
+        };
+
+        let mut step = small.sieve_search(small.length, 0);
+
+        while let Some(index) = step {
+            let converted_step = index * 2 + 1;
+
+            let rem = (base % converted_step as u32)
+                .iter_u32_digits()
+                .next()
+                .unwrap_or(0) as usize;
+
+            // Take each odd multiple of the prime out of the sieve.
+            let mut start = converted_step - rem;
+
+            if start.is_multiple_of(2) {
+                start += converted_step;
+            }
+
+            sieve.sieve_single(search_len, (start - 1) / 2, converted_step);
+
+            step = small.sieve_search(small.length, index + 1);
+        }
+
+        sieve
+    }
+
+    #[inline]
+    fn get(&self, index: usize) -> bool {
+        self.bits[unit_index(index)] & bit(index) != 0
+    }
+
+    #[inline]
+    fn set(&mut self, index: usize) {
+        self.bits[unit_index(index)] |= bit(index);
+    }
+
+    /// Find the first clear bit from `start`, never reporting `limit - 1`,
+    /// like Java's.
+    fn sieve_search(&self, limit: usize, start: usize) -> Option<usize> {
+        if start >= limit {
+            return None;
+        }
+
+        let mut index = start;
+
+        loop {
+            if !self.get(index) {
+                return Some(index);
+            }
+
+            index += 1;
+
+            if index >= limit - 1 {
+                return None;
+            }
+        }
+    }
+
+    fn sieve_single(&mut self, limit: usize, mut start: usize, step: usize) {
+        while start < limit {
+            self.set(start);
+            start += step;
+        }
+    }
+
+    /// Test the remaining candidates in order, returning the first prime.
+    fn retrieve<R: Source + ?Sized>(
+        &self,
+        base: &BigUint,
+        certainty: i32,
+        rng: &mut R,
+    ) -> Option<BigUint> {
+        let mut offset = 1_u32;
+
+        for &unit in &self.bits {
+            let mut next = !unit;
+
+            for _ in 0..64 {
+                if next & 1 == 1 {
+                    let candidate = base + offset;
+
+                    if prime_to_certainty(&candidate, certainty, rng) {
+                        return Some(candidate);
+                    }
+                }
+
+                next >>= 1;
+                offset += 2;
+            }
+        }
+
+        None
+    }
+}
+
+#[inline]
+const fn unit_index(index: usize) -> usize {
+    index >> 6
+}
+
+#[inline]
+const fn bit(index: usize) -> u64 {
+    1 << (index & 63)
+}
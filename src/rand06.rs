@@ -0,0 +1,49 @@
+//! The `rand_core` 0.6 traits, so [`JavaRng`] works with `rand` 0.8.
+
+use rand_core::{Error, RngCore, SeedableRng};
+
+use crate::JavaRng;
+
+/// The same values as the inherent methods: `next_u32` and `next_u64` are
+/// `nextInt()` and `nextLong()`, and `fill_bytes` is `nextBytes`.
+impl RngCore for JavaRng {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        JavaRng::next_u32(self)
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        JavaRng::next_u64(self)
+    }
+
+    #[inline]
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.next_bytes(dest);
+    }
+
+    #[inline]
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.next_bytes(dest);
+
+        Ok(())
+    }
+}
+
+/// The seed is the `long` given to `new Random(seed)`, in little endian.
+///
+/// Unlike the default, [`SeedableRng::seed_from_u64`] uses its `state` as
+/// that `long` directly, so it matches Java too.
+impl SeedableRng for JavaRng {
+    type Seed = [u8; 8];
+
+    #[inline]
+    fn from_seed(seed: [u8; 8]) -> JavaRng {
+        JavaRng::with_seed(i64::from_le_bytes(seed))
+    }
+
+    #[inline]
+    fn seed_from_u64(state: u64) -> JavaRng {
+        JavaRng::with_seed(state as i64)
+    }
+}
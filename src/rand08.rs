@@ -0,0 +1,148 @@
+//! Java's algorithms for `rand` 0.8's [`Distribution`] and [`Fill`], so code
+//! mixing `rand` and jrand draws the same values from a [`JavaRng`] either
+//! way.
+//!
+//! [`JavaRng`]: crate::JavaRng
+
+use core::ops::Range;
+
+use rand::{distributions::Distribution, Error, Fill, Rng, RngCore};
+
+use crate::UniformJava;
+
+/// Random number generator running Java's algorithms on the `next_u32`
+/// values of any `rand` generator, which stand in for `nextInt()`.
+///
+/// It has the same methods as [`JavaRng`](crate::JavaRng), and wrapping a
+/// `JavaRng` in it gives the same values as the `JavaRng` itself.
+#[derive(Debug, Clone, Default)]
+pub struct RandRng<R> {
+    rng: R,
+    pub(crate) next_gaussian: Option<f64>,
+}
+
+impl<R: RngCore> RandRng<R> {
+    /// Create a random number generator drawing from `rng`.
+    #[inline]
+    #[must_use]
+    pub const fn new(rng: R) -> RandRng<R> {
+        RandRng {
+            rng,
+            next_gaussian: None,
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn rng(&self) -> &R {
+        &self.rng
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn rng_mut(&mut self) -> &mut R {
+        &mut self.rng
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> R {
+        self.rng
+    }
+}
+
+impl<R: RngCore> RandRng<R> {
+    #[inline]
+    #[must_use]
+    pub(crate) fn next(&mut self, bits: u8) -> i32 {
+        (self.rng.next_u32() >> (32 - bits)) as i32
+    }
+}
+
+impl_random!([R: RngCore] RandRng<R>);
+
+impl<R: RngCore> RandRng<R> {
+    #[inline]
+    pub fn next_bytes(&mut self, bytes: &mut [u8]) {
+        self.rng.fill_bytes(bytes);
+    }
+}
+
+impl<R: RngCore> From<R> for RandRng<R> {
+    #[inline]
+    fn from(rng: R) -> RandRng<R> {
+        RandRng::new(rng)
+    }
+}
+
+/// Distribution of the values in a range, generated with the ranged method
+/// Java has for their type, as [`UniformJava`] does.
+///
+/// Like `rand`'s `Uniform`, it panics on an empty range, though only once
+/// sampled rather than when created.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct JavaUniform<T> {
+    range: Range<T>,
+}
+
+impl<T: UniformJava> JavaUniform<T> {
+    /// Create a distribution over `range`.
+    #[inline]
+    #[must_use]
+    pub const fn new(range: Range<T>) -> JavaUniform<T> {
+        JavaUniform { range }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn range(&self) -> &Range<T> {
+        &self.range
+    }
+}
+
+impl<T: UniformJava + Clone> Distribution<T> for JavaUniform<T> {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> T {
+        T::sample_java(&mut RandRng::new(rng), self.range.clone())
+    }
+}
+
+impl<T: UniformJava> From<Range<T>> for JavaUniform<T> {
+    #[inline]
+    fn from(range: Range<T>) -> JavaUniform<T> {
+        JavaUniform::new(range)
+    }
+}
+
+/// Slice to fill with [`Rng::fill`] the way the `fill_*` methods do, with
+/// `nextInt()`, `nextLong()`, `nextBoolean()`, `nextFloat()` or
+/// `nextDouble()` for each element.
+///
+/// `rand`'s own `Fill` for integer slices converts random bytes instead,
+/// which differs from `nextLong()` for 64-bit ones, and it has none for
+/// `bool` or floats.
+#[derive(Debug)]
+pub struct JavaFill<'a, T>(pub &'a mut [T]);
+
+macro_rules! impl_fill {
+    ($($ty:ty => $fill:ident),* $(,)?) => {
+        $(
+            impl Fill for JavaFill<'_, $ty> {
+                #[inline]
+                fn try_fill<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Result<(), Error> {
+                    RandRng::new(rng).$fill(self.0);
+
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+impl_fill! {
+    i32 => fill_i32,
+    i64 => fill_i64,
+    bool => fill_bool,
+    f32 => fill_f32,
+    f64 => fill_f64,
+}
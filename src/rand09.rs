@@ -0,0 +1,44 @@
+//! The `rand_core` 0.9 traits, so [`JavaRng`] works with `rand` 0.9.
+//!
+//! `TryRngCore` comes from `rand_core`'s blanket impl over [`RngCore`], with
+//! an error of [`Infallible`](core::convert::Infallible).
+
+use rand_core_0_9::{RngCore, SeedableRng};
+
+use crate::JavaRng;
+
+/// The same values as the inherent methods: `next_u32` and `next_u64` are
+/// `nextInt()` and `nextLong()`, and `fill_bytes` is `nextBytes`.
+impl RngCore for JavaRng {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        JavaRng::next_u32(self)
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        JavaRng::next_u64(self)
+    }
+
+    #[inline]
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.next_bytes(dest);
+    }
+}
+
+/// The seed is the `long` given to `new Random(seed)`, in little endian, and
+/// [`SeedableRng::seed_from_u64`] uses its `state` as that `long`, as with
+/// `rand_core` 0.6.
+impl SeedableRng for JavaRng {
+    type Seed = [u8; 8];
+
+    #[inline]
+    fn from_seed(seed: [u8; 8]) -> JavaRng {
+        JavaRng::with_seed(i64::from_le_bytes(seed))
+    }
+
+    #[inline]
+    fn seed_from_u64(state: u64) -> JavaRng {
+        JavaRng::with_seed(state as i64)
+    }
+}
@@ -1,7 +1,13 @@
-use super::math;
 use core::{
-    iter::{repeat_with, FusedIterator},
-    ops::Range,
+    fmt,
+    hash::{Hash, Hasher},
+    iter::FusedIterator,
+    mem::MaybeUninit,
+};
+
+use crate::{
+    entropy::{EntropyError, EntropySource},
+    FromJavaRng,
 };
 
 /// Random number generator that replicates the behavior of
@@ -26,6 +32,19 @@ impl JavaRng {
         }
     }
 
+    /// Create a random number generator with the specified internal
+    /// `state`, skipping the scramble of the seed.
+    ///
+    /// Only the low 48 bits of `state` are used.
+    #[inline]
+    #[must_use]
+    pub const fn from_state(state: i64) -> JavaRng {
+        JavaRng {
+            seed: state & consts::MASK,
+            next_gaussian: None,
+        }
+    }
+
     /// Create a random number generator with a seed of zero.
     #[inline]
     #[must_use]
@@ -50,14 +69,16 @@ impl JavaRng {
     /// With the `std` feature enabled, it is equivalent to calling
     /// [`JavaRng::new_nanos`].
     ///
-    /// With the `std` feature disabled, it is equivalent to calling
-    /// [`JavaRng::new_zeroed`].
+    /// With the `std` feature disabled, it is seeded from
+    /// [`DefaultSource`](crate::entropy::DefaultSource), which uses
+    /// hardware or operating system entropy where it can, and is otherwise
+    /// equivalent to calling [`JavaRng::new_zeroed`].
     #[inline]
     #[must_use]
     pub fn new() -> JavaRng {
         #[cfg(not(feature = "std"))]
         {
-            JavaRng::new_zeroed()
+            JavaRng::from_entropy(crate::entropy::DefaultSource)
         }
 
         #[cfg(feature = "std")]
@@ -65,50 +86,166 @@ impl JavaRng {
             JavaRng::new_nanos()
         }
     }
+
+    /// Create a random number generator seeded with an `i64` from `source`,
+    /// which is scrambled like any seed given to [`JavaRng::with_seed`].
+    ///
+    /// This needs neither `std` nor a clock, so it is how `no_std` code gets
+    /// a seed that is not fixed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the source fails, see [`JavaRng::try_from_entropy`].
+    #[inline]
+    #[must_use]
+    pub fn from_entropy(mut source: impl EntropySource) -> JavaRng {
+        JavaRng::with_seed(source.next_i64())
+    }
+
+    /// Like [`JavaRng::from_entropy`], but returns the error if the source
+    /// fails.
+    #[inline]
+    pub fn try_from_entropy(mut source: impl EntropySource) -> Result<JavaRng, EntropyError> {
+        source.try_next_i64().map(JavaRng::with_seed)
+    }
+
+    /// Like [`JavaRng::try_from_entropy`], but awaits an async `source`.
+    #[cfg(feature = "async")]
+    #[inline]
+    pub async fn from_async_entropy(
+        mut source: impl crate::entropy::AsyncEntropySource,
+    ) -> Result<JavaRng, EntropyError> {
+        source.try_next_i64().await.map(JavaRng::with_seed)
+    }
+
+    /// Reset the random number generator to the specified `seed`.
+    ///
+    /// This is equivalent to calling `setSeed` in Java, which also discards
+    /// the cached gaussian.
+    #[inline]
+    pub fn set_seed(&mut self, seed: i64) {
+        self.seed = initial_scramble(seed);
+        self.next_gaussian = None;
+    }
+
+    /// Reset the random number generator to a seed from `source`, like
+    /// [`JavaRng::set_seed`].
+    #[inline]
+    pub fn reseed(&mut self, mut source: impl EntropySource) {
+        self.set_seed(source.next_i64());
+    }
+
+    /// Like [`JavaRng::reseed`], but leaves the random number generator
+    /// untouched and returns the error if the source fails.
+    #[inline]
+    pub fn try_reseed(&mut self, mut source: impl EntropySource) -> Result<(), EntropyError> {
+        self.set_seed(source.try_next_i64()?);
+
+        Ok(())
+    }
+
+    /// Get the internal 48-bit state of the random number generator.
+    #[inline]
+    #[must_use]
+    pub const fn state(&self) -> i64 {
+        self.seed
+    }
+
+    /// Set the internal state of the random number generator, skipping
+    /// the scramble of the seed.
+    ///
+    /// Only the low 48 bits of `state` are used, and the cached gaussian
+    /// is kept.
+    #[inline]
+    pub fn set_state(&mut self, state: i64) {
+        self.seed = state & consts::MASK;
+    }
+
+    /// Get the seed that would create a random number generator with the
+    /// current internal state, assuming it has not generated anything yet.
+    ///
+    /// Java only keeps the low 48 bits of a seed, so any seed with the same
+    /// low 48 bits as the result is equally valid.
+    #[inline]
+    #[must_use]
+    pub const fn original_seed(&self) -> i64 {
+        descramble(self.seed)
+    }
+
+    /// Get the gaussian cached by the last call to
+    /// [`JavaRng::next_gaussian`], if any.
+    #[inline]
+    #[must_use]
+    pub const fn cached_gaussian(&self) -> Option<f64> {
+        self.next_gaussian
+    }
+
+    /// Set the gaussian the next call to [`JavaRng::next_gaussian`]
+    /// returns, or clear it with `None`.
+    #[inline]
+    pub fn set_cached_gaussian(&mut self, gaussian: Option<f64>) {
+        self.next_gaussian = gaussian;
+    }
 }
 
 impl JavaRng {
     #[inline]
     #[must_use]
-    pub(crate) fn next(&mut self, bits: u8) -> i32 {
+    pub(crate) const fn next(&mut self, bits: u8) -> i32 {
         self.seed = next_seed(self.seed);
 
         (self.seed as u64 >> (48 - bits)) as i32
     }
 }
 
+impl_random!(JavaRng);
+
+/// `const` counterparts of the basic methods, which take the random number
+/// generator by value and return it advanced alongside the value, such as
+/// `(rng, x) = rng.step_i32()`.
+///
+/// Each generates exactly what the method it is named after does, so tables
+/// derived from a Java seed can be computed at compile time.
 impl JavaRng {
+    /// Like [`JavaRng::next_i32`].
     #[inline]
     #[must_use]
-    pub fn next_bytes(&mut self, bytes: &mut [u8]) {
-        bytes.chunks_mut(4).for_each(|chunk| {
-            let bytes = self.next_i32().to_le_bytes();
-
-            chunk.copy_from_slice(&bytes[..chunk.len()])
-        });
+    pub const fn step_i32(mut self) -> (JavaRng, i32) {
+        let x = self.next(32);
+        (self, x)
     }
 
+    /// Like [`JavaRng::next_i64`].
     #[inline]
     #[must_use]
-    pub fn next_bytes_signed(&mut self, bytes: &mut [i8]) {
-        self.next_bytes(bytemuck::cast_slice_mut(bytes))
+    pub const fn step_i64(mut self) -> (JavaRng, i64) {
+        let x = self.const_next_i64();
+        (self, x)
     }
 
+    /// Like [`JavaRng::next_bool`].
     #[inline]
     #[must_use]
-    pub fn next_i32(&mut self) -> i32 {
-        self.next(32)
+    pub const fn step_bool(mut self) -> (JavaRng, bool) {
+        let x = self.next(1) != 0;
+        (self, x)
     }
 
+    /// Like [`JavaRng::next_i32_bounded`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bound` is not positive.
     #[inline]
     #[must_use]
-    pub fn next_i32_bounded(&mut self, bound: i32) -> i32 {
+    pub const fn step_i32_bounded(mut self, bound: i32) -> (JavaRng, i32) {
         assert!(bound > 0, "bound must be positive");
 
         let max = bound - 1;
 
         if bound & max == 0 {
-            return ((self.next(31) as i64).wrapping_mul(bound as i64) >> 31) as i32;
+            let x = ((self.next(31) as i64).wrapping_mul(bound as i64) >> 31) as i32;
+            return (self, x);
         }
 
         loop {
@@ -116,239 +253,374 @@ impl JavaRng {
             let rem = bits % bound;
 
             if bits.wrapping_sub(rem).wrapping_add(max) >= 0 {
-                break rem;
+                break (self, rem);
             }
         }
     }
 
+    /// Like [`JavaRng::next_i64_bounded`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bound` is not positive.
     #[inline]
     #[must_use]
-    pub fn next_i32_ranged(&mut self, range: Range<i32>) -> i32 {
-        let Range {
-            start: origin,
-            end: bound,
-        } = range;
-
-        let len = bound.wrapping_sub(origin);
-
-        if origin >= bound {
-            self.next_i32()
-        } else if len > 0 {
-            self.next_i32_bounded(len) + origin
-        } else {
-            loop {
-                let r = self.next_i32();
-
-                if (origin..bound).contains(&r) {
-                    break r;
-                }
+    pub const fn step_i64_bounded(mut self, bound: i64) -> (JavaRng, i64) {
+        assert!(bound > 0, "bound must be positive");
+
+        let max = bound - 1;
+        let bits = self.const_next_i64();
+
+        if bound & max == 0 {
+            return (self, bits & max);
+        }
+
+        let mut bits = (bits as u64 >> 1) as i64;
+
+        loop {
+            let rem = bits % bound;
+
+            if bits.wrapping_add(max).wrapping_sub(rem) >= 0 {
+                break (self, rem);
             }
+
+            bits = (self.const_next_i64() as u64 >> 1) as i64;
         }
     }
 
     #[inline]
-    #[must_use]
-    pub fn i32_iter(&mut self) -> impl Iterator<Item = i32> + FusedIterator + '_ {
-        repeat_with(|| self.next_i32())
+    const fn const_next_i64(&mut self) -> i64 {
+        let upper = (self.next(32) as i64) << 32;
+        let lower = self.next(32) as i64;
+
+        upper.wrapping_add(lower)
     }
+}
 
+impl JavaRng {
     #[inline]
-    #[must_use]
-    pub fn i32_iter_bounded(
-        &mut self,
-        bound: i32,
-    ) -> impl Iterator<Item = i32> + FusedIterator + '_ {
-        repeat_with(move || self.next_i32_bounded(bound))
+    pub fn next_bytes(&mut self, bytes: &mut [u8]) {
+        bytes.chunks_mut(4).for_each(|chunk| {
+            let bytes = self.next_i32().to_le_bytes();
+
+            chunk.copy_from_slice(&bytes[..chunk.len()])
+        });
     }
 
+    /// Fill `bytes` like [`JavaRng::next_bytes`], without needing them to be
+    /// initialized first, and return them initialized.
     #[inline]
-    #[must_use]
-    pub fn i32_iter_ranged(
+    pub fn next_bytes_uninit<'a>(&mut self, bytes: &'a mut [MaybeUninit<u8>]) -> &'a mut [u8] {
+        bytes.chunks_mut(4).for_each(|chunk| {
+            let bytes = self.next_i32().to_le_bytes();
+
+            for (dest, &byte) in chunk.iter_mut().zip(&bytes) {
+                dest.write(byte);
+            }
+        });
+
+        // SAFETY: Every byte was written above, and `MaybeUninit<u8>` has the
+        // same layout as `u8`.
+        unsafe { &mut *(bytes as *mut [MaybeUninit<u8>] as *mut [u8]) }
+    }
+
+    /// Write `len` bytes to `writer`, the same bytes as one call to
+    /// [`JavaRng::next_bytes`] would generate, returning how many were
+    /// written.
+    ///
+    /// The bytes are generated in chunks, so the random number generator
+    /// has advanced past the failed chunk if an error is returned.
+    #[cfg(feature = "std")]
+    pub fn write_bytes<W: std::io::Write + ?Sized>(
         &mut self,
-        range: Range<i32>,
-    ) -> impl Iterator<Item = i32> + FusedIterator + '_ {
-        repeat_with(move || self.next_i32_ranged(range.clone()))
+        writer: &mut W,
+        len: u64,
+    ) -> std::io::Result<u64> {
+        // A multiple of 4, so each chunk ends with a whole `next_i32`.
+        let mut buf = [0; 8192];
+        let mut written = 0;
+
+        while written < len {
+            let n = (len - written).min(buf.len() as u64) as usize;
+
+            self.next_bytes(&mut buf[..n]);
+            writer.write_all(&buf[..n])?;
+
+            written += n as u64;
+        }
+
+        Ok(written)
     }
+}
 
+impl JavaRng {
+    /// Advance the random number generator as if `next` were called `n`
+    /// times, in `O(log n)`.
+    ///
+    /// The cached gaussian is kept.
     #[inline]
-    #[must_use]
-    pub fn next_u32(&mut self) -> u32 {
-        self.next_i32() as u32
+    pub fn skip(&mut self, n: u64) {
+        self.seed = jump(self.seed, n);
     }
 
+    /// Get a copy of the random number generator advanced as if `next` were
+    /// called `n` times.
     #[inline]
     #[must_use]
-    pub fn next_i64(&mut self) -> i64 {
-        let upper = (self.next_i32() as i64) << 32;
-        let lower = self.next_i32() as i64;
-
-        upper.wrapping_add(lower)
+    pub const fn skipped(mut self, n: u64) -> JavaRng {
+        self.seed = jump(self.seed, n);
+        self
     }
 
+    /// Rewind the random number generator as if `next` had been called `n`
+    /// fewer times, in `O(log n)`.
+    ///
+    /// The cached gaussian is kept.
     #[inline]
-    #[must_use]
-    pub fn next_i64_ranged(&mut self, range: Range<i64>) -> i64 {
-        let Range {
-            start: origin,
-            end: bound,
-        } = range;
-
-        let len = bound.wrapping_sub(origin);
-        let max = len.wrapping_sub(1);
-
-        if origin >= bound {
-            self.next_i64()
-        } else if len & max == 0 {
-            (self.next_i64() & max).wrapping_add(origin)
-        } else if len > 0 {
-            loop {
-                let bits = (self.next_u64() >> 1) as i64;
-                let rem = bits % len;
-
-                if bits.wrapping_add(max).wrapping_sub(rem) >= 0 {
-                    break rem + origin;
-                }
-            }
-        } else {
-            loop {
-                let r = self.next_i64();
-
-                if (origin..bound).contains(&r) {
-                    break r;
-                }
-            }
-        }
+    pub fn step_back(&mut self, n: u64) {
+        // The generator has a period of 2^48, which divides 2^64.
+        self.seed = jump(self.seed, n.wrapping_neg());
     }
 
+    /// Get how many times `next` must be called to advance this random
+    /// number generator to the state of `other`.
+    ///
+    /// The generator visits every 48-bit state in its period, so this is
+    /// below `2^48` and only `None` if a state has bits set above the low
+    /// 48, which only a deserialized generator can have.
     #[inline]
     #[must_use]
-    pub fn i64_iter(&mut self) -> impl Iterator<Item = i64> + FusedIterator + '_ {
-        repeat_with(|| self.next_i64())
+    pub const fn distance_to(&self, other: &JavaRng) -> Option<u64> {
+        distance(self.seed, other.seed)
     }
 
+    /// Rewind the random number generator by one [`JavaRng::next_i32`],
+    /// returning the value it generated.
     #[inline]
     #[must_use]
-    pub fn i64_iter_ranged(
-        &mut self,
-        range: Range<i64>,
-    ) -> impl Iterator<Item = i64> + FusedIterator + '_ {
-        repeat_with(move || self.next_i64_ranged(range.clone()))
+    pub fn previous_i32(&mut self) -> i32 {
+        let value = (self.seed as u64 >> 16) as i32;
+        self.seed = previous_seed(self.seed);
+
+        value
     }
 
+    /// Rewind the random number generator by one [`JavaRng::next_i64`],
+    /// returning the value it generated.
     #[inline]
     #[must_use]
-    pub fn next_u64(&mut self) -> u64 {
-        self.next_i64() as u64
+    pub fn previous_i64(&mut self) -> i64 {
+        let lower = self.previous_i32() as i64;
+        let upper = (self.previous_i32() as i64) << 32;
+
+        upper.wrapping_add(lower)
     }
+}
 
+impl JavaRng {
+    /// Get the value the next call to [`JavaRng::next_i32`] returns,
+    /// without advancing the random number generator.
     #[inline]
     #[must_use]
-    pub fn next_bool(&mut self) -> bool {
-        self.next(1) != 0
+    pub fn peek_i32(&self) -> i32 {
+        JavaRng::from_state(self.seed).next_i32()
     }
 
+    /// Get the value the next call to [`JavaRng::next_i64`] returns,
+    /// without advancing the random number generator.
     #[inline]
     #[must_use]
-    pub fn next_f32(&mut self) -> f32 {
-        (self.next(24) as f32) * consts::FLOAT_UNIT
+    pub fn peek_i64(&self) -> i64 {
+        JavaRng::from_state(self.seed).next_i64()
     }
 
+    /// Get the value the next call to [`JavaRng::next_f64`] returns,
+    /// without advancing the random number generator.
     #[inline]
     #[must_use]
-    pub fn next_f64(&mut self) -> f64 {
-        let upper = (self.next(26) as i64) << 27;
-        let lower = self.next(27) as i64;
-
-        (upper.wrapping_add(lower) as f64) * consts::DOUBLE_UNIT
+    pub fn peek_f64(&self) -> f64 {
+        JavaRng::from_state(self.seed).next_f64()
     }
+}
 
+impl JavaRng {
+    /// Create a child random number generator seeded with the next `i64`,
+    /// like the Java idiom `new Random(rnd.nextLong())`.
     #[inline]
     #[must_use]
-    pub fn next_f64_ranged(&mut self, range: Range<f64>) -> f64 {
-        let Range {
-            start: origin,
-            end: bound,
-        } = range;
-
-        let mut r = self.next_f64();
-
-        if origin < bound {
-            r = math::mul_add(r, bound - origin, origin);
-
-            if r >= bound {
-                r = f64::from_bits(r.to_bits().wrapping_sub(1));
-            }
-        }
+    pub fn fork(&mut self) -> JavaRng {
+        JavaRng::with_seed(self.next_i64())
+    }
 
-        r
+    /// Create `n` children in turn, like calling [`JavaRng::fork`] `n`
+    /// times.
+    #[inline]
+    pub fn fork_n(
+        &mut self,
+        n: usize,
+    ) -> impl ExactSizeIterator<Item = JavaRng> + FusedIterator + '_ {
+        (0..n).map(|_| self.fork())
     }
+}
 
+impl JavaRng {
+    /// Generate a value of any type implementing [`FromJavaRng`], such as
+    /// `rng.random::<(i32, [bool; 2])>()`.
     #[inline]
     #[must_use]
-    pub fn f64_iter(&mut self) -> impl Iterator<Item = f64> + FusedIterator + '_ {
-        repeat_with(|| self.next_f64())
+    pub fn random<T: FromJavaRng>(&mut self) -> T {
+        T::from_rng(self)
     }
+}
 
+impl Default for JavaRng {
+    fn default() -> Self {
+        JavaRng::new()
+    }
+}
+
+/// Two random number generators are equal when they will generate the same
+/// values, meaning their states are equal and they have the same gaussian
+/// cached, compared bit for bit.
+impl PartialEq for JavaRng {
     #[inline]
-    #[must_use]
-    pub fn f64_iter_ranged(
-        &mut self,
-        range: Range<f64>,
-    ) -> impl Iterator<Item = f64> + FusedIterator + '_ {
-        repeat_with(move || self.next_f64_ranged(range.clone()))
+    fn eq(&self, other: &JavaRng) -> bool {
+        self.seed == other.seed
+            && self.next_gaussian.map(f64::to_bits) == other.next_gaussian.map(f64::to_bits)
     }
+}
+
+impl Eq for JavaRng {}
 
+impl Hash for JavaRng {
     #[inline]
-    #[must_use]
-    pub fn next_gaussian(&mut self) -> f64 {
-        if let Some(next) = self.next_gaussian.take() {
-            return next;
-        }
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.seed.hash(state);
+        self.next_gaussian.map(f64::to_bits).hash(state);
+    }
+}
 
-        let (v1, v2) = repeat_with(|| {
-            let v1 = math::mul_add(2., self.next_f64(), -1.);
-            let v2 = math::mul_add(2., self.next_f64(), -1.);
-            let s = (v1 * v1) + (v2 * v2);
+/// Errors reported for an invalid bound or range, mirroring the
+/// `IllegalArgumentException`s Java throws.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BoundError {
+    /// An integer bound is not positive.
+    NotPositive,
+    /// A floating-point bound is not finite and positive.
+    NotFiniteAndPositive,
+    /// A range is empty, or too wide to generate floating-point values in.
+    InvalidRange,
+}
 
-            (v1, v2, s)
+impl fmt::Display for BoundError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            BoundError::NotPositive => "bound must be positive",
+            BoundError::NotFiniteAndPositive => "bound must be finite and positive",
+            BoundError::InvalidRange => "bound must be greater than origin",
         })
-        .find(|(.., s)| *s < 1. && *s != 0.)
-        .map(|(v1, v2, s)| {
-            let multiplier = math::sqrt(-2. * math::ln(s) / s);
+    }
+}
 
-            (v1 * multiplier, v2 * multiplier)
-        })
-        .expect("failed to generate next gaussian values");
+#[cfg(feature = "std")]
+impl std::error::Error for BoundError {}
 
-        self.next_gaussian = Some(v2);
+/// Generate a random `f64` in `[0, 1)` like Java's `Math.random()`.
+///
+/// All calls share one [`JavaRng`], created with [`JavaRng::new_nanos`]
+/// the first time this is called.
+#[inline]
+#[must_use]
+#[cfg(feature = "std")]
+pub fn math_random() -> f64 {
+    use std::sync::{Mutex, OnceLock, PoisonError};
 
-        v1
-    }
-}
+    static RNG: OnceLock<Mutex<JavaRng>> = OnceLock::new();
 
-impl Default for JavaRng {
-    fn default() -> Self {
-        JavaRng::new()
-    }
+    RNG.get_or_init(|| Mutex::new(JavaRng::new_nanos()))
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .next_f64()
 }
 
+/// Invert the scramble Java applies to the seed of a `java.util.Random`,
+/// turning an internal `state` back into the low 48 bits of the seed.
 #[inline]
 #[must_use]
-const fn initial_scramble(seed: i64) -> i64 {
+pub const fn descramble(state: i64) -> i64 {
+    (state ^ consts::MULTIPLIER) & consts::MASK
+}
+
+#[inline]
+pub(crate) const fn initial_scramble(seed: i64) -> i64 {
     (seed ^ consts::MULTIPLIER) & consts::MASK
 }
 
 #[inline]
-#[must_use]
-const fn next_seed(seed: i64) -> i64 {
+pub(crate) const fn next_seed(seed: i64) -> i64 {
     seed.wrapping_mul(consts::MULTIPLIER)
         .wrapping_add(consts::ADDEND)
         & consts::MASK
 }
 
+#[inline]
+const fn previous_seed(seed: i64) -> i64 {
+    seed.wrapping_sub(consts::ADDEND)
+        .wrapping_mul(consts::MULTIPLIER_INVERSE)
+        & consts::MASK
+}
+
+/// Find the steps from `from` to `to`, one bit at a time.
+///
+/// Jumping `2^i` steps keeps the low `i` bits of the state and flips bit
+/// `i`, so each bit of the distance is decided by one bit of the states.
+const fn distance(from: i64, to: i64) -> Option<u64> {
+    if (from | to) & !consts::MASK != 0 {
+        return None;
+    }
+
+    let mut state = from;
+    let mut n = 0_u64;
+    let (mut step_mul, mut step_add) = (consts::MULTIPLIER, consts::ADDEND);
+    let mut i = 0;
+
+    while i < 48 {
+        if (state ^ to) >> i & 1 != 0 {
+            state = state.wrapping_mul(step_mul).wrapping_add(step_add) & consts::MASK;
+            n |= 1 << i;
+        }
+
+        step_add = step_mul.wrapping_add(1).wrapping_mul(step_add);
+        step_mul = step_mul.wrapping_mul(step_mul);
+        i += 1;
+    }
+
+    Some(n)
+}
+
+/// Advance `seed` by `n` steps, composing the steps by squaring.
+#[inline]
+const fn jump(seed: i64, mut n: u64) -> i64 {
+    let (mut mul, mut add) = (1_i64, 0_i64);
+    let (mut step_mul, mut step_add) = (consts::MULTIPLIER, consts::ADDEND);
+
+    while n != 0 {
+        if n & 1 != 0 {
+            mul = mul.wrapping_mul(step_mul);
+            add = add.wrapping_mul(step_mul).wrapping_add(step_add);
+        }
+
+        step_add = step_mul.wrapping_add(1).wrapping_mul(step_add);
+        step_mul = step_mul.wrapping_mul(step_mul);
+        n >>= 1;
+    }
+
+    seed.wrapping_mul(mul).wrapping_add(add) & consts::MASK
+}
+
 #[cfg(feature = "std")]
 fn get_seed() -> i64 {
-    use core::sync::atomic::{AtomicI64, Ordering};
+    use crate::atomic::{AtomicI64, Ordering};
     use std::time::SystemTime;
 
     let uniquifier = {
@@ -391,6 +663,7 @@ pub mod consts {
     pub const DOUBLE_UNIT: f64 = 1.1102230246251565E-16;
 
     pub const MULTIPLIER: i64 = 0x5DEECE66D;
+    pub const MULTIPLIER_INVERSE: i64 = 0xDFE05BCB1365;
     pub const ADDEND: i64 = 0xB;
     pub const MASK: i64 = (1 << 48) - 1;
 
@@ -0,0 +1,208 @@
+use core::fmt;
+
+#[cfg(feature = "drbg")]
+use crate::drbg::{Drbg, DrbgConfig, DrbgError};
+#[cfg(feature = "getrandom")]
+use crate::NativeRng;
+#[cfg(feature = "sha1prng")]
+use crate::Sha1Prng;
+
+/// `SecureRandom.next(bits)`: read just enough big-endian bytes to hold `bits`.
+#[inline]
+pub(crate) fn secure_next(bits: u8, next_bytes: impl FnOnce(&mut [u8])) -> i32 {
+    let len = (bits as usize).div_ceil(8);
+    let mut bytes = [0; 4];
+
+    next_bytes(&mut bytes[..len]);
+
+    let next = bytes[..len]
+        .iter()
+        .fold(0u32, |next, &byte| (next << 8) | byte as u32);
+
+    (next >> (len * 8 - bits as usize)) as i32
+}
+
+/// Error returned by [`SecureRandomCompat::get_instance`] for an algorithm
+/// that is unknown or whose feature is disabled, like Java's
+/// `NoSuchAlgorithmException`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NoSuchAlgorithmError;
+
+impl fmt::Display for NoSuchAlgorithmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("no such SecureRandom algorithm")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NoSuchAlgorithmError {}
+
+// A DRBG dwarfs the other engines, and there is no allocator to box it.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone)]
+enum Engine {
+    #[cfg(feature = "sha1prng")]
+    Sha1Prng(Sha1Prng),
+    /// Java instantiates a DRBG lazily, so that a first `setSeed` becomes
+    /// its entropy input.
+    #[cfg(feature = "drbg")]
+    Drbg(DrbgConfig, Option<Drbg>),
+    #[cfg(feature = "getrandom")]
+    Native(&'static str, NativeRng),
+}
+
+/// A `java.security.SecureRandom` whose algorithm is chosen by name at
+/// runtime, like `SecureRandom.getInstance(algorithm)`.
+///
+/// It has the same methods as [`JavaRng`](crate::JavaRng), all derived from
+/// [`SecureRandomCompat::next_bytes`] the way `SecureRandom` derives them.
+#[derive(Debug, Clone)]
+pub struct SecureRandomCompat {
+    engine: Engine,
+    pub(crate) next_gaussian: Option<f64>,
+}
+
+impl SecureRandomCompat {
+    /// Create an unseeded generator for `algorithm`, matched
+    /// case-insensitively like Java's providers do.
+    ///
+    /// The supported algorithms are `SHA1PRNG` (with the `sha1prng`
+    /// feature), `DRBG` (with the `drbg` feature) and `NativePRNG`,
+    /// `NativePRNGBlocking` and `NativePRNGNonBlocking` (with the
+    /// `getrandom` feature).
+    pub fn get_instance(algorithm: &str) -> Result<SecureRandomCompat, NoSuchAlgorithmError> {
+        fn is(algorithm: &str, name: &str) -> bool {
+            algorithm.eq_ignore_ascii_case(name)
+        }
+
+        let engine = match algorithm {
+            #[cfg(feature = "sha1prng")]
+            algorithm if is(algorithm, "SHA1PRNG") => Engine::Sha1Prng(Sha1Prng::new()),
+            #[cfg(feature = "drbg")]
+            algorithm if is(algorithm, "DRBG") => Engine::Drbg(DrbgConfig::new(), None),
+            #[cfg(feature = "getrandom")]
+            algorithm if is(algorithm, "NativePRNG") => {
+                Engine::Native("NativePRNG", NativeRng::new())
+            }
+            #[cfg(feature = "getrandom")]
+            algorithm if is(algorithm, "NativePRNGBlocking") => {
+                Engine::Native("NativePRNGBlocking", NativeRng::new())
+            }
+            #[cfg(feature = "getrandom")]
+            algorithm if is(algorithm, "NativePRNGNonBlocking") => {
+                Engine::Native("NativePRNGNonBlocking", NativeRng::new())
+            }
+            _ => return Err(NoSuchAlgorithmError),
+        };
+
+        Ok(SecureRandomCompat::from_engine(engine))
+    }
+
+    /// Create an unseeded `DRBG` with the given configuration, like
+    /// `SecureRandom.getInstance("DRBG", params)`.
+    #[cfg(feature = "drbg")]
+    pub fn get_instance_drbg(config: DrbgConfig) -> Result<SecureRandomCompat, DrbgError> {
+        Drbg::validate(config)?;
+
+        Ok(SecureRandomCompat::from_engine(Engine::Drbg(config, None)))
+    }
+
+    #[inline]
+    const fn from_engine(engine: Engine) -> SecureRandomCompat {
+        SecureRandomCompat {
+            engine,
+            next_gaussian: None,
+        }
+    }
+
+    /// The standard name of the algorithm, like `getAlgorithm()`.
+    #[inline]
+    #[must_use]
+    pub fn algorithm(&self) -> &'static str {
+        match &self.engine {
+            #[cfg(feature = "sha1prng")]
+            Engine::Sha1Prng(_) => "SHA1PRNG",
+            #[cfg(feature = "drbg")]
+            Engine::Drbg(..) => "DRBG",
+            #[cfg(feature = "getrandom")]
+            Engine::Native(name, _) => name,
+        }
+    }
+
+    /// Supplement or replace the seed, like `setSeed(byte[])`.
+    ///
+    /// Seeding `SHA1PRNG` or `DRBG` before its first use makes the output
+    /// reproducible. The native algorithms ignore the seed, as their output is
+    /// never reproducible.
+    pub fn set_seed(&mut self, seed: &[u8]) {
+        match &mut self.engine {
+            #[cfg(feature = "sha1prng")]
+            Engine::Sha1Prng(rng) => rng.set_seed(seed),
+            #[cfg(feature = "drbg")]
+            Engine::Drbg(_, Some(drbg)) => drbg.set_seed(seed),
+            #[cfg(feature = "drbg")]
+            Engine::Drbg(config, drbg @ None) => {
+                *drbg =
+                    Some(Drbg::with_seed(*config, seed).expect("the configuration was validated"))
+            }
+            #[cfg(feature = "getrandom")]
+            Engine::Native(..) => {
+                let _ = seed;
+            }
+        }
+    }
+
+    /// Supplement or replace the seed with the little-endian bytes of `seed`,
+    /// like `setSeed(long)`, which ignores a seed of zero.
+    #[inline]
+    pub fn set_seed_i64(&mut self, seed: i64) {
+        if seed != 0 {
+            self.set_seed(&seed.to_le_bytes());
+        }
+    }
+
+    #[inline]
+    pub fn next_bytes(&mut self, bytes: &mut [u8]) {
+        match &mut self.engine {
+            #[cfg(feature = "sha1prng")]
+            Engine::Sha1Prng(rng) => rng.next_bytes(bytes),
+            #[cfg(feature = "drbg")]
+            Engine::Drbg(config, drbg) => drbg
+                .get_or_insert_with(|| self_seed_drbg(*config))
+                .next_bytes(bytes),
+            #[cfg(feature = "getrandom")]
+            Engine::Native(_, rng) => rng.next_bytes(bytes),
+        }
+    }
+}
+
+impl SecureRandomCompat {
+    #[inline]
+    #[must_use]
+    pub(crate) fn next(&mut self, bits: u8) -> i32 {
+        secure_next(bits, |bytes| self.next_bytes(bytes))
+    }
+}
+
+impl_random!(SecureRandomCompat);
+
+/// Instantiate an unseeded DRBG from the operating system, which requires the
+/// `getrandom` feature.
+#[cfg(feature = "drbg")]
+fn self_seed_drbg(config: DrbgConfig) -> Drbg {
+    #[cfg(feature = "getrandom")]
+    {
+        let mut seed = [0; 64];
+        getrandom::getrandom(&mut seed)
+            .expect("failed to get random bytes from the operating system");
+
+        Drbg::with_seed(config, &seed).expect("the configuration was validated")
+    }
+
+    #[cfg(not(feature = "getrandom"))]
+    {
+        let _ = config;
+
+        panic!("DRBG must be seeded before use without the `getrandom` feature")
+    }
+}
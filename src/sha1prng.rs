@@ -0,0 +1,156 @@
+use crate::secure::secure_next;
+use sha1::{Digest, Sha1};
+
+mod android;
+pub use android::*;
+
+const DIGEST_SIZE: usize = 20;
+
+/// Random number generator that replicates the behavior of Java's
+/// `SHA1PRNG` `SecureRandom`.
+///
+/// Seeding it before the first use makes its output reproducible, like
+/// calling `setSeed` on `SecureRandom.getInstance("SHA1PRNG")`. Otherwise it
+/// seeds itself from the operating system, which requires the `getrandom`
+/// feature.
+#[derive(Debug, Clone, Default)]
+pub struct Sha1Prng {
+    state: Option<[u8; DIGEST_SIZE]>,
+    remainder: [u8; DIGEST_SIZE],
+    rem_count: usize,
+    pub(crate) next_gaussian: Option<f64>,
+}
+
+impl Sha1Prng {
+    /// Create an unseeded generator.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Sha1Prng {
+        Sha1Prng {
+            state: None,
+            remainder: [0; DIGEST_SIZE],
+            rem_count: 0,
+            next_gaussian: None,
+        }
+    }
+
+    /// Create a generator seeded with `seed`.
+    #[inline]
+    #[must_use]
+    pub fn with_seed(seed: &[u8]) -> Sha1Prng {
+        let mut rng = Sha1Prng::new();
+        rng.set_seed(seed);
+        rng
+    }
+
+    /// Mix `seed` into the state, like `setSeed(byte[])`.
+    ///
+    /// The first seed replaces the state, later ones are hashed together
+    /// with it.
+    pub fn set_seed(&mut self, seed: &[u8]) {
+        let mut digest = Sha1::new();
+
+        if let Some(state) = &self.state {
+            digest.update(state);
+        }
+
+        digest.update(seed);
+
+        self.state = Some(digest.finalize().into());
+        self.rem_count = 0;
+    }
+
+    /// Mix the little-endian bytes of `seed` into the state, like
+    /// `setSeed(long)`, which ignores a seed of zero.
+    #[inline]
+    pub fn set_seed_i64(&mut self, seed: i64) {
+        if seed != 0 {
+            self.set_seed(&seed.to_le_bytes());
+        }
+    }
+}
+
+impl Sha1Prng {
+    #[inline]
+    #[must_use]
+    pub(crate) fn next(&mut self, bits: u8) -> i32 {
+        secure_next(bits, |bytes| self.next_bytes(bytes))
+    }
+}
+
+impl_random!(Sha1Prng);
+
+impl Sha1Prng {
+    pub fn next_bytes(&mut self, bytes: &mut [u8]) {
+        let state = self.state.get_or_insert_with(self_seed);
+
+        // Use the rest of the last digest first.
+        let r = self.rem_count;
+        let mut index = 0;
+
+        if r > 0 {
+            let todo = bytes.len().min(DIGEST_SIZE - r);
+
+            bytes[..todo].copy_from_slice(&self.remainder[r..r + todo]);
+
+            self.rem_count += todo;
+            index += todo;
+        }
+
+        while index < bytes.len() {
+            let output: [u8; DIGEST_SIZE] = Sha1::digest(*state).into();
+            update_state(state, &output);
+
+            let todo = (bytes.len() - index).min(DIGEST_SIZE);
+
+            bytes[index..index + todo].copy_from_slice(&output[..todo]);
+
+            self.remainder = output;
+            self.rem_count += todo;
+            index += todo;
+        }
+
+        self.rem_count %= DIGEST_SIZE;
+    }
+}
+
+/// Seed an unseeded generator from the operating system, like Java's seed
+/// generator.
+fn self_seed() -> [u8; DIGEST_SIZE] {
+    #[cfg(feature = "getrandom")]
+    {
+        let mut seed = [0; DIGEST_SIZE];
+        getrandom::getrandom(&mut seed)
+            .expect("failed to get random bytes from the operating system");
+
+        Sha1::digest(seed).into()
+    }
+
+    #[cfg(not(feature = "getrandom"))]
+    {
+        panic!("SHA1PRNG must be seeded before use without the `getrandom` feature")
+    }
+}
+
+/// `state = state + output + 1`, carrying through the bytes as Java does.
+///
+/// Java adds the bytes as signed values, so the carries differ from a true
+/// 160-bit addition.
+fn update_state(state: &mut [u8; DIGEST_SIZE], output: &[u8; DIGEST_SIZE]) {
+    let mut last = 1;
+    let mut changed = false;
+
+    for (s, &o) in state.iter_mut().zip(output) {
+        let v = *s as i8 as i32 + o as i8 as i32 + last;
+        let t = v as u8;
+
+        changed |= *s != t;
+        *s = t;
+        last = v >> 8;
+    }
+
+    // Make sure at least one bit changes.
+    if !changed {
+        state[0] = state[0].wrapping_add(1);
+    }
+}
@@ -0,0 +1,389 @@
+use crate::secure::secure_next;
+use core::fmt;
+
+/// Where the byte count of the current frame lives in the state words.
+const BYTES_OFFSET: usize = 81;
+/// Where the five hash words live in the state words.
+const HASH_OFFSET: usize = 82;
+const DIGEST_LENGTH: usize = 20;
+
+const FRAME_LENGTH: usize = 16;
+const HASHCOPY_OFFSET: usize = 0;
+const EXTRAFRAME_OFFSET: usize = 5;
+const FRAME_OFFSET: usize = 21;
+const MAX_BYTES: u32 = 48;
+
+const END_FLAGS: [u32; 4] = [0x8000_0000, 0x0080_0000, 0x0000_8000, 0x0000_0080];
+const RIGHT1: [u32; 4] = [0, 40, 48, 56];
+const RIGHT2: [u32; 4] = [0, 8, 16, 24];
+const LEFT: [u32; 4] = [0, 24, 16, 8];
+const MASK: [u32; 4] = [0xffff_ffff, 0x00ff_ffff, 0x0000_ffff, 0x0000_00ff];
+
+const H: [u32; 5] = [
+    0x6745_2301,
+    0xefcd_ab89,
+    0x98ba_dcfe,
+    0x1032_5476,
+    0xc3d2_e1f0,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Undefined,
+    SetSeed,
+    NextBytes,
+}
+
+/// Random number generator that replicates the `SHA1PRNG` of old Android
+/// releases, Apache Harmony's `SHA1PRNG_SecureRandomImpl`.
+///
+/// Its seeded output differs from Oracle's [`Sha1Prng`](crate::Sha1Prng), as
+/// it hashes the seed and counter with its own framing. Left unseeded, it
+/// keeps the bug behind the 2013 Android Bitcoin wallet thefts: the counter
+/// overwrites the first 12 bytes of its 20-byte self-seed, so only 64 bits
+/// of it affect the output. [`AndroidLegacySha1Prng::with_self_seed`]
+/// reproduces that path from known seed bytes.
+#[derive(Clone)]
+pub struct AndroidLegacySha1Prng {
+    /// The current frame, the message schedule `computeHash` leaves behind,
+    /// the frame's byte count and the hash words, laid out like Harmony's.
+    seed: [u32; HASH_OFFSET + EXTRAFRAME_OFFSET],
+    seed_length: i64,
+    copies: [u32; 2 * FRAME_LENGTH + EXTRAFRAME_OFFSET],
+    next_bytes: [u8; DIGEST_LENGTH],
+    next_index: usize,
+    counter: i64,
+    state: State,
+    pub(crate) next_gaussian: Option<f64>,
+}
+
+impl AndroidLegacySha1Prng {
+    /// Create an unseeded generator.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> AndroidLegacySha1Prng {
+        let mut seed = [0; HASH_OFFSET + EXTRAFRAME_OFFSET];
+        let mut i = 0;
+
+        while i < H.len() {
+            seed[HASH_OFFSET + i] = H[i];
+            i += 1;
+        }
+
+        AndroidLegacySha1Prng {
+            seed,
+            seed_length: 0,
+            copies: [0; 2 * FRAME_LENGTH + EXTRAFRAME_OFFSET],
+            next_bytes: [0; DIGEST_LENGTH],
+            next_index: DIGEST_LENGTH,
+            counter: 0,
+            state: State::Undefined,
+            next_gaussian: None,
+        }
+    }
+
+    /// Create a generator seeded with `seed`.
+    #[inline]
+    #[must_use]
+    pub fn with_seed(seed: &[u8]) -> AndroidLegacySha1Prng {
+        let mut rng = AndroidLegacySha1Prng::new();
+        rng.set_seed(seed);
+        rng
+    }
+
+    /// Create a generator in the state an unseeded one reaches on its first
+    /// use, given the bytes it read from the system.
+    #[inline]
+    #[must_use]
+    pub fn with_self_seed(random_bits: [u8; DIGEST_LENGTH]) -> AndroidLegacySha1Prng {
+        let mut rng = AndroidLegacySha1Prng::new();
+        rng.self_seed(random_bits);
+        rng
+    }
+
+    /// Supplement the seed, like `setSeed(byte[])`.
+    pub fn set_seed(&mut self, seed: &[u8]) {
+        if self.state == State::NextBytes {
+            // Restore the hash saved before the first output.
+            self.seed[HASH_OFFSET..HASH_OFFSET + EXTRAFRAME_OFFSET].copy_from_slice(
+                &self.copies[HASHCOPY_OFFSET..HASHCOPY_OFFSET + EXTRAFRAME_OFFSET],
+            );
+        }
+
+        self.state = State::SetSeed;
+
+        if !seed.is_empty() {
+            self.update_seed(seed);
+        }
+    }
+
+    /// Supplement the seed with the big-endian bytes of `seed`, like
+    /// Harmony's `setSeed(long)`, which ignores a seed of zero.
+    #[inline]
+    pub fn set_seed_i64(&mut self, seed: i64) {
+        if seed != 0 {
+            self.set_seed(&seed.to_be_bytes());
+        }
+    }
+
+    #[inline]
+    fn update_seed(&mut self, bytes: &[u8]) {
+        update_hash(&mut self.seed, bytes);
+        self.seed_length += bytes.len() as i64;
+    }
+
+    /// Harmony writes `(bytes + extrabytes) >> 3 - 1`, which Java parses as a
+    /// shift by two.
+    #[inline]
+    fn last_word(&self) -> usize {
+        match self.seed[BYTES_OFFSET] as usize {
+            0 => 0,
+            bytes => (bytes + 7) >> 2,
+        }
+    }
+
+    /// The unseeded path of `engineNextBytes`, which does not recompute the
+    /// last word of the seed after seeding.
+    fn self_seed(&mut self, random_bits: [u8; DIGEST_LENGTH]) -> usize {
+        let last_word = self.last_word();
+
+        self.update_seed(&random_bits);
+        self.next_index = DIGEST_LENGTH;
+        self.state = State::NextBytes;
+
+        last_word
+    }
+}
+
+impl AndroidLegacySha1Prng {
+    #[inline]
+    #[must_use]
+    pub(crate) fn next(&mut self, bits: u8) -> i32 {
+        secure_next(bits, |bytes| self.next_bytes(bytes))
+    }
+}
+
+impl_random!(AndroidLegacySha1Prng);
+
+impl AndroidLegacySha1Prng {
+    pub fn next_bytes(&mut self, bytes: &mut [u8]) {
+        let mut last_word = self.last_word();
+
+        match self.state {
+            State::Undefined => last_word = self.self_seed(system_random_bits()),
+            State::SetSeed => {
+                self.copies[HASHCOPY_OFFSET..HASHCOPY_OFFSET + EXTRAFRAME_OFFSET]
+                    .copy_from_slice(&self.seed[HASH_OFFSET..HASH_OFFSET + EXTRAFRAME_OFFSET]);
+
+                // Clear the rest of the frame, and the two words past it that
+                // a long seed spills into.
+                for word in self
+                    .seed
+                    .iter_mut()
+                    .take(FRAME_LENGTH + 2)
+                    .skip(last_word + 3)
+                {
+                    *word = 0;
+                }
+
+                let bits = (self.seed_length << 3).wrapping_add(64);
+
+                let length = if self.seed[BYTES_OFFSET] < MAX_BYTES {
+                    &mut self.seed[14..16]
+                } else {
+                    &mut self.copies[EXTRAFRAME_OFFSET + 14..EXTRAFRAME_OFFSET + 16]
+                };
+
+                length[0] = (bits as u64 >> 32) as u32;
+                length[1] = bits as u32;
+
+                self.next_index = DIGEST_LENGTH;
+            }
+            State::NextBytes => {}
+        }
+
+        self.state = State::NextBytes;
+
+        if bytes.is_empty() {
+            return;
+        }
+
+        // Use the rest of the last hash first.
+        let n = (DIGEST_LENGTH - self.next_index).min(bytes.len());
+        bytes[..n].copy_from_slice(&self.next_bytes[self.next_index..self.next_index + n]);
+        self.next_index += n;
+
+        let mut index = n;
+        let n = (self.seed[BYTES_OFFSET] & 0x03) as usize;
+        let long_seed = self.seed[BYTES_OFFSET] > MAX_BYTES;
+
+        while index < bytes.len() {
+            let counter = self.counter as u64;
+
+            if n == 0 {
+                self.seed[last_word] = (counter >> 32) as u32;
+                self.seed[last_word + 1] = counter as u32;
+                self.seed[last_word + 2] = END_FLAGS[0];
+            } else {
+                self.seed[last_word] |= (counter >> RIGHT1[n]) as u32 & MASK[n];
+                self.seed[last_word + 1] = (counter >> RIGHT2[n]) as u32;
+                self.seed[last_word + 2] = (counter << LEFT[n]) as u32 | END_FLAGS[n];
+            }
+
+            if long_seed {
+                self.copies[EXTRAFRAME_OFFSET] = self.seed[FRAME_LENGTH];
+                self.copies[EXTRAFRAME_OFFSET + 1] = self.seed[FRAME_LENGTH + 1];
+            }
+
+            compute_hash(&mut self.seed);
+
+            if long_seed {
+                // Hash the spilled frame, keeping the seed frame intact.
+                self.copies[FRAME_OFFSET..].copy_from_slice(&self.seed[..FRAME_LENGTH]);
+                self.seed[..FRAME_LENGTH].copy_from_slice(
+                    &self.copies[EXTRAFRAME_OFFSET..EXTRAFRAME_OFFSET + FRAME_LENGTH],
+                );
+                compute_hash(&mut self.seed);
+                self.seed[..FRAME_LENGTH].copy_from_slice(&self.copies[FRAME_OFFSET..]);
+            }
+
+            self.counter = self.counter.wrapping_add(1);
+
+            for (chunk, word) in self
+                .next_bytes
+                .chunks_exact_mut(4)
+                .zip(&self.seed[HASH_OFFSET..])
+            {
+                chunk.copy_from_slice(&word.to_be_bytes());
+            }
+
+            let n = DIGEST_LENGTH.min(bytes.len() - index);
+            bytes[index..index + n].copy_from_slice(&self.next_bytes[..n]);
+
+            self.next_index = n;
+            index += n;
+        }
+    }
+}
+
+impl Default for AndroidLegacySha1Prng {
+    fn default() -> Self {
+        AndroidLegacySha1Prng::new()
+    }
+}
+
+impl fmt::Debug for AndroidLegacySha1Prng {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The internal state is deliberately left out.
+        f.debug_struct("AndroidLegacySha1Prng")
+            .field("state", &self.state)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The bytes an unseeded generator reads from the system.
+fn system_random_bits() -> [u8; DIGEST_LENGTH] {
+    #[cfg(feature = "getrandom")]
+    {
+        let mut bits = [0; DIGEST_LENGTH];
+        getrandom::getrandom(&mut bits)
+            .expect("failed to get random bytes from the operating system");
+
+        bits
+    }
+
+    #[cfg(not(feature = "getrandom"))]
+    {
+        panic!("SHA1PRNG must be seeded before use without the `getrandom` feature")
+    }
+}
+
+/// Append `bytes` to the frame, hashing every frame that fills up, like
+/// Harmony's `SHA1Impl.updateHash`.
+fn update_hash(words: &mut [u32; HASH_OFFSET + EXTRAFRAME_OFFSET], bytes: &[u8]) {
+    let index = words[BYTES_OFFSET] as usize;
+    words[BYTES_OFFSET] = ((index + bytes.len()) & 0x3f) as u32;
+
+    let mut word_index = index >> 2;
+    let mut byte_index = index & 0x03;
+    let mut bytes = bytes;
+
+    // Fill the current word first.
+    if byte_index != 0 {
+        while let (Some((&byte, rest)), true) = (bytes.split_first(), byte_index < 4) {
+            words[word_index] |= (byte as u32) << ((3 - byte_index) << 3);
+            byte_index += 1;
+            bytes = rest;
+        }
+
+        if byte_index == 4 {
+            word_index += 1;
+
+            if word_index == FRAME_LENGTH {
+                compute_hash(words);
+                word_index = 0;
+            }
+        }
+
+        if bytes.is_empty() {
+            return;
+        }
+    }
+
+    let mut chunks = bytes.chunks_exact(4);
+
+    for chunk in chunks.by_ref() {
+        words[word_index] = u32::from_be_bytes(chunk.try_into().unwrap());
+        word_index += 1;
+
+        if word_index == FRAME_LENGTH {
+            compute_hash(words);
+            word_index = 0;
+        }
+    }
+
+    // The last partial word is written with zeros after the bytes.
+    let rest = chunks.remainder();
+
+    if !rest.is_empty() {
+        let mut word = [0; 4];
+        word[..rest.len()].copy_from_slice(rest);
+        words[word_index] = u32::from_be_bytes(word);
+    }
+}
+
+/// Hash the frame into the hash words, leaving the message schedule behind
+/// it, like Harmony's `SHA1Impl.computeHash`.
+fn compute_hash(words: &mut [u32; HASH_OFFSET + EXTRAFRAME_OFFSET]) {
+    for t in 16..80 {
+        words[t] = (words[t - 3] ^ words[t - 8] ^ words[t - 14] ^ words[t - 16]).rotate_left(1);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e] = [0, 1, 2, 3, 4].map(|i| words[HASH_OFFSET + i]);
+
+    for (t, &w) in words[..80].iter().enumerate() {
+        let (f, k) = match t {
+            0..=19 => ((b & c) | (!b & d), 0x5a82_7999),
+            20..=39 => (b ^ c ^ d, 0x6ed9_eba1),
+            40..=59 => ((b & c) | (b & d) | (c & d), 0x8f1b_bcdc),
+            _ => (b ^ c ^ d, 0xca62_c1d6_u32),
+        };
+
+        let temp = a
+            .rotate_left(5)
+            .wrapping_add(f)
+            .wrapping_add(e)
+            .wrapping_add(w)
+            .wrapping_add(k);
+
+        e = d;
+        d = c;
+        c = b.rotate_left(30);
+        b = a;
+        a = temp;
+    }
+
+    for (h, x) in words[HASH_OFFSET..].iter_mut().zip([a, b, c, d, e]) {
+        *h = h.wrapping_add(x);
+    }
+}
@@ -0,0 +1,110 @@
+//! The "brine" derivation Java 17's LXM generators use when splitting.
+//!
+//! A splittable generator built on `AbstractSplittableWithBrineGenerator`
+//! makes every child with `split(source, brine)`, picking the child's LCG
+//! multiplier from the brine and the rest of its state from
+//! `source.nextLong()`:
+//!
+//! - `split(source)` uses `source.nextLong()` as the brine.
+//! - `splits(n, source)` draws a [`salt`] from the splitting generator, then
+//!   uses the [`Brines`] for it, one per child.
+//!
+//! Feeding these to a Java-exact `split(source, brine)` gives split trees
+//! identical to Java's.
+
+use core::iter::FusedIterator;
+
+/// How many bits each salt digit takes.
+const SALT_SHIFT: u32 = 4;
+
+/// Derive the salt for `splits`, like `makeSplitsSpliterator`.
+///
+/// This consumes one `nextLong()` of the generator being split, turning it
+/// into fifteen base-15 digits topped by a nibble of ones, so no brine of a
+/// stream collides with another stream's.
+#[inline]
+#[must_use]
+pub fn salt(mut next_i64: impl FnMut() -> i64) -> i64 {
+    const MULTIPLIER: i64 = (1 << SALT_SHIFT) - 1;
+
+    let mut bits = next_i64();
+    let mut salt = MULTIPLIER << (64 - SALT_SHIFT);
+
+    while salt & MULTIPLIER == 0 {
+        // `Math.multiplyHigh`.
+        let digit = ((bits as i128 * MULTIPLIER as i128) >> 64) as i64;
+
+        salt = (salt as u64 >> SALT_SHIFT) as i64 | digit << (64 - SALT_SHIFT);
+        bits = bits.wrapping_mul(MULTIPLIER);
+    }
+
+    salt
+}
+
+/// The brines `splits` passes to `split(source, brine)` for its children,
+/// like `RandomSplitsSpliteratorWithSalt`.
+///
+/// Each brine is the child's index with the salt above it; the salt moves up
+/// a digit whenever the index would reach it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Brines {
+    salt: i64,
+    index: i64,
+    fence: i64,
+}
+
+impl Brines {
+    /// Create the brines of the children with indices in `index..fence`.
+    #[inline]
+    #[must_use]
+    pub const fn new(salt: i64, index: i64, fence: i64) -> Brines {
+        let mut salt = salt;
+
+        while salt != 0 && (salt & salt.wrapping_neg()) as u64 <= index as u64 {
+            salt <<= SALT_SHIFT;
+        }
+
+        Brines { salt, index, fence }
+    }
+
+    /// Create the brines of a stream of `len` children, like
+    /// `splits(len, source)`.
+    #[inline]
+    #[must_use]
+    pub const fn with_len(salt: i64, len: i64) -> Brines {
+        Brines::new(salt, 0, len)
+    }
+}
+
+impl Iterator for Brines {
+    type Item = i64;
+
+    #[inline]
+    fn next(&mut self) -> Option<i64> {
+        if self.index >= self.fence {
+            return None;
+        }
+
+        let brine = self.salt | self.index;
+
+        self.index += 1;
+
+        if self.index & self.salt != 0 {
+            self.salt <<= SALT_SHIFT;
+        }
+
+        Some(brine)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.fence.saturating_sub(self.index).max(0) as u64;
+
+        match usize::try_from(len) {
+            Ok(len) => (len, Some(len)),
+            Err(_) => (usize::MAX, None),
+        }
+    }
+}
+
+impl FusedIterator for Brines {}
@@ -0,0 +1,1491 @@
+//! Ports of the fdlibm functions Java's `StrictMath` is specified to use,
+//! giving bit-for-bit the same results as Java on every target.
+//!
+//! These are what the generators here use without `std` or `libm`, and are
+//! exposed for code that needs to match Java beyond the random numbers
+//! themselves, such as noise or distributions built on `StrictMath`.
+//!
+//! [`log2`] is the one function Java lacks, ported from FreeBSD in the same
+//! style instead.
+
+/// Get the high word of `x`, with the sign and exponent.
+#[inline(always)]
+const fn high(x: f64) -> i32 {
+    (x.to_bits() >> 32) as i32
+}
+
+/// Get the low word of `x`.
+#[inline(always)]
+const fn low(x: f64) -> u32 {
+    x.to_bits() as u32
+}
+
+/// Make a double from its high and low words.
+#[inline(always)]
+const fn from_words(high: i32, low: u32) -> f64 {
+    f64::from_bits(((high as u32 as u64) << 32) | low as u64)
+}
+
+/// Replace the high word of `x`.
+#[inline(always)]
+const fn with_high(x: f64, high: i32) -> f64 {
+    from_words(high, low(x))
+}
+
+/// Clear the low word of `x`.
+#[inline(always)]
+const fn without_low(x: f64) -> f64 {
+    from_words(high(x), 0)
+}
+
+const LN2_HI: f64 = 6.931471803691238e-1;
+const LN2_LO: f64 = 1.9082149292705877e-10;
+
+/// 2<sup>54</sup>, to scale subnormals up by.
+const TWO54: f64 = 1.8014398509481984e16;
+
+/// 2<sup>24</sup>, the base `rem_pio2` splits numbers in.
+const TWO24: f64 = 1.6777216e7;
+const TWON24: f64 = 5.960464477539063e-8;
+
+const HUGE: f64 = 1.0e300;
+const TINY: f64 = 1.0e-300;
+
+/// The coefficients of the polynomial `exp` and `pow` approximate
+/// `exp(r)` with.
+const P: [f64; 5] = [
+    1.6666666666666602e-1,
+    -2.7777777777015593e-3,
+    6.613756321437934e-5,
+    -1.6533902205465252e-6,
+    4.1381367970572385e-8,
+];
+
+/// The coefficients of the polynomial `log` and `log1p` approximate
+/// `log(1 + f)` with.
+const LG: [f64; 7] = [
+    6.666666666666735e-1,
+    3.999999999940942e-1,
+    2.857142874366239e-1,
+    2.2222198432149784e-1,
+    1.818357216161805e-1,
+    1.5313837699209373e-1,
+    1.4798198605116586e-1,
+];
+
+/// fdlibm's `scalbn`, multiplying `x` by 2<sup>n</sup>.
+#[inline]
+fn scalbn(mut x: f64, n: i32) -> f64 {
+    const TWOM54: f64 = 5.551115123125783e-17;
+
+    let mut hx = high(x);
+    let mut k = (hx & 0x7ff0_0000) >> 20;
+
+    if k == 0 {
+        if (low(x) | (hx & 0x7fff_ffff) as u32) == 0 {
+            return x;
+        }
+
+        x *= TWO54;
+        hx = high(x);
+        k = ((hx & 0x7ff0_0000) >> 20) - 54;
+
+        if n < -50000 {
+            return TINY * x;
+        }
+    }
+
+    if k == 0x7ff {
+        return x + x;
+    }
+
+    k += n;
+
+    if k > 0x7fe {
+        return HUGE * HUGE.copysign(x);
+    } else if k > 0 {
+        return with_high(x, (hx & 0x800f_ffffu32 as i32) | (k << 20));
+    } else if k <= -54 {
+        return if n > 50000 {
+            HUGE * HUGE.copysign(x)
+        } else {
+            TINY * TINY.copysign(x)
+        };
+    }
+
+    with_high(x, (hx & 0x800f_ffffu32 as i32) | ((k + 54) << 20)) * TWOM54
+}
+
+/// The natural logarithm of `x`, a port of fdlibm's `__ieee754_log`.
+#[inline]
+#[must_use]
+pub fn log(mut x: f64) -> f64 {
+    let mut hx = high(x);
+    let mut k = 0;
+
+    if hx < 0x0010_0000 {
+        if (hx & 0x7fff_ffff) as u32 | low(x) == 0 {
+            return f64::NEG_INFINITY;
+        } else if hx < 0 {
+            return f64::NAN;
+        }
+
+        // Subnormal, scale it up.
+        k -= 54;
+        x *= TWO54;
+        hx = high(x);
+    }
+
+    if hx >= 0x7ff0_0000 {
+        return x + x;
+    }
+
+    k += (hx >> 20) - 1023;
+    hx &= 0x000f_ffff;
+
+    // Normalize x or x / 2 into [sqrt(2) / 2, sqrt(2)).
+    let i = (hx + 0x95f64) & 0x0010_0000;
+
+    x = with_high(x, hx | (i ^ 0x3ff0_0000));
+    k += i >> 20;
+
+    let f = x - 1.0;
+    let dk = k as f64;
+
+    if (0x000f_ffff & (2 + hx)) < 3 {
+        // |f| < 2^-20
+        if f == 0.0 {
+            return if k == 0 {
+                0.0
+            } else {
+                dk * LN2_HI + dk * LN2_LO
+            };
+        }
+
+        let r = f * f * (0.5 - 0.333_333_333_333_333_3 * f);
+
+        return if k == 0 {
+            f - r
+        } else {
+            dk * LN2_HI - ((r - dk * LN2_LO) - f)
+        };
+    }
+
+    let s = f / (2.0 + f);
+    let z = s * s;
+    let w = z * z;
+
+    let t1 = w * (LG[1] + w * (LG[3] + w * LG[5]));
+    let t2 = z * (LG[0] + w * (LG[2] + w * (LG[4] + w * LG[6])));
+    let r = t2 + t1;
+
+    if ((hx - 0x6147a) | (0x6b851 - hx)) > 0 {
+        let hfsq = 0.5 * f * f;
+
+        if k == 0 {
+            f - (hfsq - s * (hfsq + r))
+        } else {
+            dk * LN2_HI - ((hfsq - (s * (hfsq + r) + dk * LN2_LO)) - f)
+        }
+    } else if k == 0 {
+        f - s * (f - r)
+    } else {
+        dk * LN2_HI - ((s * (f - r) - dk * LN2_LO) - f)
+    }
+}
+
+/// The base 10 logarithm of `x`, a port of fdlibm's `__ieee754_log10`.
+///
+/// This is within 2 ulps of the exact result, with the largest errors
+/// near 1, and exact for the powers of 10 a double holds exactly.
+#[inline]
+#[must_use]
+pub fn log10(mut x: f64) -> f64 {
+    const IVLN10: f64 = ::core::f64::consts::LOG10_E;
+    const LOG10_2HI: f64 = 3.0102999566361177e-1;
+    const LOG10_2LO: f64 = 3.694239077158931e-13;
+
+    let mut hx = high(x);
+    let mut k = 0;
+
+    if hx < 0x0010_0000 {
+        if (hx & 0x7fff_ffff) as u32 | low(x) == 0 {
+            return f64::NEG_INFINITY;
+        } else if hx < 0 {
+            return f64::NAN;
+        }
+
+        // Subnormal, scale it up.
+        k -= 54;
+        x *= TWO54;
+        hx = high(x);
+    }
+
+    if hx >= 0x7ff0_0000 {
+        return x + x;
+    }
+
+    k += (hx >> 20) - 1023;
+
+    // Take the mantissa in [1, 2), or [0.5, 1) when k is negative.
+    let i = (k as u32 >> 31) as i32;
+    let y = (k + i) as f64;
+
+    x = with_high(x, (hx & 0x000f_ffff) | ((0x3ff - i) << 20));
+
+    let z = y * LOG10_2LO + IVLN10 * log(x);
+
+    z + y * LOG10_2HI
+}
+
+/// The base 2 logarithm of `x`, a port of FreeBSD's `__ieee754_log2`, as
+/// Java has none.
+///
+/// This is within an ulp of the exact result, and exact for powers of 2.
+#[inline]
+#[must_use]
+pub fn log2(mut x: f64) -> f64 {
+    const IVLN2HI: f64 = 1.4426950407214463;
+    const IVLN2LO: f64 = 1.6751713164886512e-10;
+
+    let mut hx = high(x);
+    let mut k = 0;
+
+    if hx < 0x0010_0000 {
+        if (hx & 0x7fff_ffff) as u32 | low(x) == 0 {
+            return f64::NEG_INFINITY;
+        } else if hx < 0 {
+            return f64::NAN;
+        }
+
+        // Subnormal, scale it up.
+        k -= 54;
+        x *= TWO54;
+        hx = high(x);
+    }
+
+    if hx >= 0x7ff0_0000 {
+        return x + x;
+    } else if x == 1.0 {
+        return 0.0;
+    }
+
+    k += (hx >> 20) - 1023;
+    hx &= 0x000f_ffff;
+
+    // Normalize x or x / 2 into [sqrt(2) / 2, sqrt(2)).
+    let i = (hx + 0x95f64) & 0x0010_0000;
+
+    x = with_high(x, hx | (i ^ 0x3ff0_0000));
+    k += i >> 20;
+
+    let y = k as f64;
+    let f = x - 1.0;
+    let hfsq = 0.5 * f * f;
+
+    // log(1 + f) - f + f^2 / 2
+    let s = f / (2.0 + f);
+    let z = s * s;
+    let w = z * z;
+    let t1 = w * (LG[1] + w * (LG[3] + w * LG[5]));
+    let t2 = z * (LG[0] + w * (LG[2] + w * (LG[4] + w * LG[6])));
+    let r = s * (hfsq + (t2 + t1));
+
+    // Split log(1 + f) into hi + lo, so hi * IVLN2HI is exact.
+    let hi = without_low(f - hfsq);
+    let lo = (f - hi) - hfsq + r;
+    let mut val_hi = hi * IVLN2HI;
+    let mut val_lo = (lo + hi) * IVLN2LO + lo * IVLN2HI;
+
+    // Add k without losing val_lo.
+    let w = y + val_hi;
+
+    val_lo += (y - w) + val_hi;
+    val_hi = w;
+
+    val_lo + val_hi
+}
+
+/// The natural logarithm of `1 + x`, accurate even when `x` is near zero,
+/// a port of fdlibm's `log1p`.
+#[inline]
+#[must_use]
+pub fn log1p(x: f64) -> f64 {
+    let hx = high(x);
+    let ax = hx & 0x7fff_ffff;
+
+    let mut k = 1;
+    let mut f = 0.0;
+    let mut c = 0.0;
+    let mut hu = 0;
+
+    if hx < 0x3fda_827a {
+        // x < 0.41422
+        if ax >= 0x3ff0_0000 {
+            // x <= -1
+            return if x == -1.0 {
+                f64::NEG_INFINITY
+            } else {
+                f64::NAN
+            };
+        }
+
+        if ax < 0x3e20_0000 {
+            // |x| < 2^-29
+            return if ax < 0x3c90_0000 { x } else { x - x * x * 0.5 };
+        }
+
+        if hx > 0 || hx <= 0xbfd2_bec3u32 as i32 {
+            // -0.2929 < x < 0.41422, so log(1 + x) needs no reduction.
+            k = 0;
+            f = x;
+            hu = 1;
+        }
+    }
+
+    if hx >= 0x7ff0_0000 {
+        return x + x;
+    }
+
+    if k != 0 {
+        let mut u = x;
+
+        if hx < 0x4340_0000 {
+            u = 1.0 + x;
+            hu = high(u);
+            k = (hu >> 20) - 1023;
+            // The rounding error of 1 + x.
+            c = (if k > 0 { 1.0 - (u - x) } else { x - (u - 1.0) }) / u;
+        } else {
+            hu = high(u);
+            k = (hu >> 20) - 1023;
+        }
+
+        hu &= 0x000f_ffff;
+
+        // Normalize u or u / 2 into [sqrt(2) / 2, sqrt(2)).
+        if hu < 0x6a09e {
+            u = with_high(u, hu | 0x3ff0_0000);
+        } else {
+            k += 1;
+            u = with_high(u, hu | 0x3fe0_0000);
+            hu = (0x0010_0000 - hu) >> 2;
+        }
+
+        f = u - 1.0;
+    }
+
+    let hfsq = 0.5 * f * f;
+    let dk = k as f64;
+
+    if hu == 0 {
+        // |f| < 2^-20
+        if f == 0.0 {
+            return if k == 0 {
+                0.0
+            } else {
+                dk * LN2_HI + (c + dk * LN2_LO)
+            };
+        }
+
+        let r = hfsq * (1.0 - 0.666_666_666_666_666_6 * f);
+
+        return if k == 0 {
+            f - r
+        } else {
+            dk * LN2_HI - ((r - (dk * LN2_LO + c)) - f)
+        };
+    }
+
+    let s = f / (2.0 + f);
+    let z = s * s;
+    let r = z
+        * (LG[0] + z * (LG[1] + z * (LG[2] + z * (LG[3] + z * (LG[4] + z * (LG[5] + z * LG[6]))))));
+
+    if k == 0 {
+        f - (hfsq - s * (hfsq + r))
+    } else {
+        dk * LN2_HI - ((hfsq - (s * (hfsq + r) + (dk * LN2_LO + c))) - f)
+    }
+}
+
+/// The correctly rounded square root of `x`, computed on the integer
+/// significand, as IEEE 754 and so `StrictMath.sqrt` require.
+#[inline]
+#[must_use]
+pub fn sqrt(x: f64) -> f64 {
+    const IMPLICIT: u64 = 1 << 52;
+
+    if x.is_nan() {
+        return x + x;
+    } else if x == 0.0 || x == f64::INFINITY {
+        return x;
+    } else if x < 0.0 {
+        return f64::NAN;
+    }
+
+    let bits = x.to_bits();
+    let biased = (bits >> 52) as i32;
+
+    // x is m * 2^e, with m normalized to 53 bits.
+    let (mut m, mut e) = if biased == 0 {
+        let shift = (bits.leading_zeros() - 11) as i32;
+
+        (bits << shift, -1074 - shift)
+    } else {
+        ((bits & (IMPLICIT - 1)) | IMPLICIT, biased - 1075)
+    };
+
+    if e & 1 != 0 {
+        m <<= 1;
+        e -= 1;
+    }
+
+    // sqrt(m * 2^52) has 53 bits, and rounds up exactly when the
+    // remainder is past r, since (r + 1/2)^2 = r^2 + r + 1/4.
+    let n = (m as u128) << 52;
+    let r = n.isqrt();
+    let r = (r + (n - r * r > r) as u128) as u64;
+
+    // r carries its implicit bit into the exponent, including when
+    // rounding made it 2^53.
+    let exponent = (e - 52) / 2 + 52 + 1023 - 1;
+
+    f64::from_bits(((exponent as u64) << 52) + r)
+}
+
+/// The cube root of `x`, a port of fdlibm's `cbrt`.
+#[inline]
+#[must_use]
+pub fn cbrt(x: f64) -> f64 {
+    // (682 - 0.03306235651) * 2^20 and (664 - 0.03306235651) * 2^20
+    const B1: i32 = 715_094_163;
+    const B2: i32 = 696_219_795;
+
+    const C: f64 = 5.428571428571428e-1;
+    const D: f64 = -7.053061224489796e-1;
+    const E: f64 = 1.4142857142857144;
+    const F: f64 = 1.6071428571428572;
+    const G: f64 = 3.5714285714285715e-1;
+
+    let hx = high(x) & 0x7fff_ffff;
+    let sign = high(x) & !0x7fff_ffff;
+
+    if hx >= 0x7ff0_0000 {
+        return x + x;
+    } else if hx as u32 | low(x) == 0 {
+        return x;
+    }
+
+    let x = with_high(x, hx);
+
+    // A rough cube root, to 5 bits.
+    let mut t = if hx < 0x0010_0000 {
+        // Subnormal, scale it up by 2^54.
+        let t = from_words(0x4350_0000, 0) * x;
+
+        with_high(t, high(t) / 3 + B2)
+    } else {
+        from_words(hx / 3 + B1, 0)
+    };
+
+    // To 23 bits.
+    let r = t * t / x;
+    let s = C + r * t;
+
+    t *= G + F / (s + E + D / s);
+
+    // Chopped to 20 bits, and made larger than the cube root.
+    t = from_words(high(t) + 1, 0);
+
+    // One Newton iteration to 53 bits, with an error under 0.667 ulps.
+    let s = t * t;
+    let mut r = x / s;
+    let w = t + t;
+
+    r = (r - t) / (w + r);
+    t += t * r;
+
+    with_high(t, high(t) | sign)
+}
+
+/// The length of the hypotenuse with sides `x` and `y`, without overflow
+/// or underflow in between, a port of fdlibm's `__ieee754_hypot`.
+#[inline]
+#[must_use]
+pub fn hypot(x: f64, y: f64) -> f64 {
+    let (mut ha, mut hb) = (high(x) & 0x7fff_ffff, high(y) & 0x7fff_ffff);
+    let (mut a, mut b) = (x, y);
+
+    if hb > ha {
+        (a, b) = (y, x);
+        (ha, hb) = (hb, ha);
+    }
+
+    a = with_high(a, ha);
+    b = with_high(b, hb);
+
+    if ha - hb > 0x03c0_0000 {
+        // a / b > 2^60
+        return a + b;
+    }
+
+    let mut k = 0;
+
+    if ha > 0x5f30_0000 {
+        // a > 2^500
+        if ha >= 0x7ff0_0000 {
+            // Infinity wins over NaN.
+            return if (ha & 0x000f_ffff) as u32 | low(a) == 0 {
+                a
+            } else if (hb ^ 0x7ff0_0000) as u32 | low(b) == 0 {
+                b
+            } else {
+                a + b
+            };
+        }
+
+        // Scale a and b by 2^-600.
+        ha -= 0x2580_0000;
+        hb -= 0x2580_0000;
+        k += 600;
+        a = with_high(a, ha);
+        b = with_high(b, hb);
+    }
+
+    if hb < 0x20b0_0000 {
+        // b < 2^-500
+        if hb <= 0x000f_ffff {
+            // Subnormal or zero.
+            if hb as u32 | low(b) == 0 {
+                return a;
+            }
+
+            // Scale a and b by 2^1022. fdlibm leaves ha and hb as they were
+            // here, and so does Java.
+            let t1 = from_words(0x7fd0_0000, 0);
+
+            b *= t1;
+            a *= t1;
+            k -= 1022;
+        } else {
+            // Scale a and b by 2^600.
+            ha += 0x2580_0000;
+            hb += 0x2580_0000;
+            k -= 600;
+            a = with_high(a, ha);
+            b = with_high(b, hb);
+        }
+    }
+
+    let mut w = a - b;
+
+    if w > b {
+        let t1 = from_words(ha, 0);
+        let t2 = a - t1;
+
+        w = sqrt(t1 * t1 - (b * (-b) - t2 * (a + t1)));
+    } else {
+        a += a;
+
+        let y1 = from_words(hb, 0);
+        let y2 = b - y1;
+        let t1 = from_words(ha + 0x0010_0000, 0);
+        let t2 = a - t1;
+
+        w = sqrt(t1 * y1 - (w * (-w) - (t1 * y2 + t2 * b)));
+    }
+
+    if k != 0 {
+        from_words(0x3ff0_0000 + (k << 20), 0) * w
+    } else {
+        w
+    }
+}
+
+/// `e` raised to `x`, a port of fdlibm's `__ieee754_exp`.
+#[inline]
+#[must_use]
+pub fn exp(mut x: f64) -> f64 {
+    let hx = high(x) & 0x7fff_ffff;
+    let negative = x.is_sign_negative();
+
+    if hx >= 0x4086_2e42 {
+        if x.is_nan() {
+            return x + x;
+        } else if x > 7.09782712893384e2 {
+            return f64::INFINITY;
+        } else if x < -7.451332191019411e2 {
+            return 0.0;
+        }
+    }
+
+    let (k, hi, lo) = if hx > 0x3fd6_2e42 {
+        let (k, hi, lo) = if hx < 0x3ff0_a2b2 {
+            match negative {
+                false => (1, x - LN2_HI, LN2_LO),
+                true => (-1, x + LN2_HI, -LN2_LO),
+            }
+        } else {
+            let k = (::core::f64::consts::LOG2_E * x + if negative { -0.5 } else { 0.5 }) as i32;
+            let t = k as f64;
+
+            (k, x - t * LN2_HI, t * LN2_LO)
+        };
+
+        x = hi - lo;
+
+        (k, hi, lo)
+    } else if hx < 0x3e30_0000 {
+        return 1.0 + x;
+    } else {
+        (0, 0.0, 0.0)
+    };
+
+    let t = x * x;
+    let c = x - t * (P[0] + t * (P[1] + t * (P[2] + t * (P[3] + t * P[4]))));
+
+    if k == 0 {
+        return 1.0 - ((x * c) / (c - 2.0) - x);
+    }
+
+    let y = 1.0 - ((lo - (x * c) / (2.0 - c)) - hi);
+
+    if k >= -1021 {
+        f64::from_bits(y.to_bits().wrapping_add((k as i64 as u64) << 52))
+    } else {
+        f64::from_bits(y.to_bits().wrapping_add(((k + 1000) as i64 as u64) << 52))
+            * f64::from_bits(0x0170_0000_0000_0000)
+    }
+}
+
+/// `e` raised to `x`, minus one, accurate even when `x` is near zero, a
+/// port of fdlibm's `expm1`.
+#[inline]
+#[must_use]
+pub fn expm1(mut x: f64) -> f64 {
+    const Q: [f64; 5] = [
+        -3.333333333333313e-2,
+        1.5873015872548146e-3,
+        -7.93650757867488e-5,
+        4.008217827329362e-6,
+        -2.0109921818362437e-7,
+    ];
+
+    let hx = high(x) & 0x7fff_ffff;
+    let negative = x.is_sign_negative();
+
+    if hx >= 0x4043_687a {
+        // |x| >= 56 * ln(2)
+        if hx >= 0x4086_2e42 {
+            if x.is_nan() {
+                return x + x;
+            } else if x == f64::INFINITY {
+                return x;
+            } else if x > 7.09782712893384e2 {
+                return f64::INFINITY;
+            }
+        }
+
+        if negative {
+            return -1.0;
+        }
+    }
+
+    let (k, c) = if hx > 0x3fd6_2e42 {
+        let (k, hi, lo) = if hx < 0x3ff0_a2b2 {
+            match negative {
+                false => (1, x - LN2_HI, LN2_LO),
+                true => (-1, x + LN2_HI, -LN2_LO),
+            }
+        } else {
+            let k = (::core::f64::consts::LOG2_E * x + if negative { -0.5 } else { 0.5 }) as i32;
+            let t = k as f64;
+
+            (k, x - t * LN2_HI, t * LN2_LO)
+        };
+
+        x = hi - lo;
+
+        (k, (hi - x) - lo)
+    } else if hx < 0x3c90_0000 {
+        // |x| < 2^-54
+        return x;
+    } else {
+        (0, 0.0)
+    };
+
+    let hfx = 0.5 * x;
+    let hxs = x * hfx;
+    let r1 = 1.0 + hxs * (Q[0] + hxs * (Q[1] + hxs * (Q[2] + hxs * (Q[3] + hxs * Q[4]))));
+    let t = 3.0 - r1 * hfx;
+    let mut e = hxs * ((r1 - t) / (6.0 - x * t));
+
+    if k == 0 {
+        return x - (x * e - hxs);
+    }
+
+    e = x * (e - c) - c;
+    e -= hxs;
+
+    if k == -1 {
+        return 0.5 * (x - e) - 0.5;
+    } else if k == 1 {
+        return if x < -0.25 {
+            -2.0 * (e - (x + 0.5))
+        } else {
+            1.0 + 2.0 * (x - e)
+        };
+    }
+
+    // Add k to the exponent of y.
+    let scale = |y: f64| with_high(y, high(y).wrapping_add(k << 20));
+
+    if k <= -2 || k > 56 {
+        scale(1.0 - (e - x)) - 1.0
+    } else if k < 20 {
+        // 1 - 2^-k
+        let t = from_words(0x3ff0_0000 - (0x0020_0000 >> k), 0);
+
+        scale(t - (e - x))
+    } else {
+        // 2^-k
+        let t = from_words((0x3ff - k) << 20, 0);
+
+        scale((x - (e + t)) + 1.0)
+    }
+}
+
+/// `x` raised to `y`, a port of fdlibm's `__ieee754_pow`.
+#[must_use]
+pub fn pow(x: f64, y: f64) -> f64 {
+    const BP: [f64; 2] = [1.0, 1.5];
+    const DP_H: [f64; 2] = [0.0, 5.849624872207642e-1];
+    const DP_L: [f64; 2] = [0.0, 1.350039202129749e-8];
+    const TWO53: f64 = 9.007199254740992e15;
+    const L: [f64; 6] = [
+        5.999999999999946e-1,
+        4.285714285785502e-1,
+        3.3333332981837743e-1,
+        2.72728123808534e-1,
+        2.3066074577556175e-1,
+        2.0697501780033842e-1,
+    ];
+    const LG2: f64 = ::core::f64::consts::LN_2;
+    const LG2_H: f64 = 6.931471824645996e-1;
+    const LG2_L: f64 = -1.904654299957768e-9;
+    // -(1024 - log2(overflow + 0.5 ulp))
+    const OVT: f64 = 8.008566259537294e-17;
+    const CP: f64 = 9.617966939259756e-1;
+    const CP_H: f64 = 9.617967009544373e-1;
+    const CP_L: f64 = -7.028461650952758e-9;
+    const IVLN2: f64 = ::core::f64::consts::LOG2_E;
+    const IVLN2_H: f64 = 1.4426950216293335;
+    const IVLN2_L: f64 = 1.9259629911266175e-8;
+
+    let (hx, lx) = (high(x), low(x));
+    let (hy, ly) = (high(y), low(y));
+    let (mut ix, iy) = (hx & 0x7fff_ffff, hy & 0x7fff_ffff);
+
+    // x^0 is 1, even for NaN.
+    if (iy as u32 | ly) == 0 {
+        return 1.0;
+    }
+
+    if ix > 0x7ff0_0000
+        || (ix == 0x7ff0_0000 && lx != 0)
+        || iy > 0x7ff0_0000
+        || (iy == 0x7ff0_0000 && ly != 0)
+    {
+        return x + y;
+    }
+
+    // Whether y is not an integer (0), an odd integer (1) or an even
+    // integer (2), when x is negative.
+    let mut yisint = 0;
+
+    if hx < 0 {
+        if iy >= 0x4340_0000 {
+            yisint = 2;
+        } else if iy >= 0x3ff0_0000 {
+            let k = (iy >> 20) - 0x3ff;
+
+            if k > 20 {
+                let j = ly >> (52 - k);
+
+                if (j << (52 - k)) == ly {
+                    yisint = 2 - (j & 1) as i32;
+                }
+            } else if ly == 0 {
+                let j = iy >> (20 - k);
+
+                if (j << (20 - k)) == iy {
+                    yisint = 2 - (j & 1);
+                }
+            }
+        }
+    }
+
+    if ly == 0 {
+        if iy == 0x7ff0_0000 {
+            // y is infinite.
+            return if ((ix - 0x3ff0_0000) as u32 | lx) == 0 {
+                f64::NAN
+            } else if ix >= 0x3ff0_0000 {
+                if hy >= 0 {
+                    y
+                } else {
+                    0.0
+                }
+            } else if hy < 0 {
+                -y
+            } else {
+                0.0
+            };
+        } else if iy == 0x3ff0_0000 {
+            return if hy < 0 { 1.0 / x } else { x };
+        } else if hy == 0x4000_0000 {
+            return x * x;
+        } else if hy == 0x3fe0_0000 && hx >= 0 {
+            return sqrt(x);
+        }
+    }
+
+    let mut ax = x.abs();
+
+    // x is 0, 1 or infinite.
+    if lx == 0 && (ix == 0x7ff0_0000 || ix == 0 || ix == 0x3ff0_0000) {
+        let mut z = ax;
+
+        if hy < 0 {
+            z = 1.0 / z;
+        }
+
+        if hx < 0 {
+            if ((ix - 0x3ff0_0000) | yisint) == 0 {
+                // (-1)^non-integer
+                z = f64::NAN;
+            } else if yisint == 1 {
+                z = -z;
+            }
+        }
+
+        return z;
+    }
+
+    let mut n = (hx >> 31) + 1;
+
+    // A negative x to a non-integer.
+    if (n | yisint) == 0 {
+        return f64::NAN;
+    }
+
+    // The sign of the result.
+    let s = if (n | (yisint - 1)) == 0 { -1.0 } else { 1.0 };
+
+    let (t1, t2) = if iy > 0x41e0_0000 {
+        // |y| > 2^31
+        if iy > 0x43f0_0000 {
+            // |y| > 2^64 must overflow or underflow.
+            if ix <= 0x3fef_ffff {
+                return if hy < 0 { HUGE * HUGE } else { TINY * TINY };
+            } else if ix >= 0x3ff0_0000 {
+                return if hy > 0 { HUGE * HUGE } else { TINY * TINY };
+            }
+        }
+
+        // So does x when it is not close to one.
+        if ix < 0x3fef_ffff {
+            return if hy < 0 {
+                s * HUGE * HUGE
+            } else {
+                s * TINY * TINY
+            };
+        } else if ix > 0x3ff0_0000 {
+            return if hy > 0 {
+                s * HUGE * HUGE
+            } else {
+                s * TINY * TINY
+            };
+        }
+
+        // |1 - x| <= 2^-20, so log(x) is x - x^2 / 2 + x^3 / 3 - x^4 / 4.
+        let t = ax - 1.0;
+        let w = (t * t) * (0.5 - t * (0.333_333_333_333_333_3 - t * 0.25));
+        let u = IVLN2_H * t;
+        let v = t * IVLN2_L - w * IVLN2;
+        let t1 = without_low(u + v);
+
+        (t1, v - (t1 - u))
+    } else {
+        n = 0;
+
+        if ix < 0x0010_0000 {
+            // Subnormal, scale it up.
+            ax *= TWO53;
+            n -= 53;
+            ix = high(ax);
+        }
+
+        n += (ix >> 20) - 0x3ff;
+
+        let j = ix & 0x000f_ffff;
+
+        ix = j | 0x3ff0_0000;
+
+        // Pick the interval.
+        let k = if j <= 0x3988e {
+            // |x| < sqrt(3 / 2)
+            0
+        } else if j < 0xbb67a {
+            // |x| < sqrt(3)
+            1
+        } else {
+            n += 1;
+            ix -= 0x0010_0000;
+
+            0
+        };
+
+        ax = with_high(ax, ix);
+
+        // ss = s_h + s_l = (x - 1) / (x + 1) or (x - 1.5) / (x + 1.5)
+        let u = ax - BP[k];
+        let v = 1.0 / (ax + BP[k]);
+        let ss = u * v;
+        let s_h = without_low(ss);
+
+        // t_h = ax + bp[k], high
+        let t_h = from_words(
+            ((ix >> 1) | 0x2000_0000) + 0x0008_0000 + ((k as i32) << 18),
+            0,
+        );
+        let t_l = ax - (t_h - BP[k]);
+        let s_l = v * ((u - s_h * t_h) - s_h * t_l);
+
+        // log(ax)
+        let mut s2 = ss * ss;
+        let mut r =
+            s2 * s2 * (L[0] + s2 * (L[1] + s2 * (L[2] + s2 * (L[3] + s2 * (L[4] + s2 * L[5])))));
+
+        r += s_l * (s_h + ss);
+        s2 = s_h * s_h;
+
+        let t_h = without_low(3.0 + s2 + r);
+        let t_l = r - ((t_h - 3.0) - s2);
+
+        // u + v = ss * (1 + ...)
+        let u = s_h * t_h;
+        let v = s_l * t_h + t_l * ss;
+
+        // 2 / (3 * log(2)) * (ss + ...)
+        let p_h = without_low(u + v);
+        let p_l = v - (p_h - u);
+        let z_h = CP_H * p_h;
+        let z_l = CP_L * p_h + p_l * CP + DP_L[k];
+
+        // log2(ax) = (ss + ...) * 2 / (3 * log(2)) = n + dp_h + z_h + z_l
+        let t = n as f64;
+        let t1 = without_low(((z_h + z_l) + DP_H[k]) + t);
+
+        (t1, z_l - (((t1 - t) - DP_H[k]) - z_h))
+    };
+
+    // Split y into y1 + y2 and compute (y1 + y2) * (t1 + t2).
+    let y1 = without_low(y);
+    let p_l = (y - y1) * t1 + y * t2;
+    let mut p_h = y1 * t1;
+    let z = p_l + p_h;
+    let (j, i) = (high(z), low(z));
+
+    if j >= 0x4090_0000 {
+        // z >= 1024
+        if ((j - 0x4090_0000) as u32 | i) != 0 || p_l + OVT > z - p_h {
+            return s * HUGE * HUGE;
+        }
+    } else if (j & 0x7fff_ffff) >= 0x4090_cc00 {
+        // z <= -1075
+        if ((j as u32).wrapping_sub(0xc090_cc00) | i) != 0 || p_l <= z - p_h {
+            return s * TINY * TINY;
+        }
+    }
+
+    // 2^(p_h + p_l)
+    let i = j & 0x7fff_ffff;
+    let mut k = (i >> 20) - 0x3ff;
+    let mut n = 0;
+
+    if i > 0x3fe0_0000 {
+        // |z| > 0.5, so n = [z + 0.5]
+        n = j + (0x0010_0000 >> (k + 1));
+        k = ((n & 0x7fff_ffff) >> 20) - 0x3ff;
+
+        let t = from_words(n & !(0x000f_ffff >> k), 0);
+
+        n = ((n & 0x000f_ffff) | 0x0010_0000) >> (20 - k);
+
+        if j < 0 {
+            n = -n;
+        }
+
+        p_h -= t;
+    }
+
+    let t = without_low(p_l + p_h);
+    let u = t * LG2_H;
+    let v = (p_l - (t - p_h)) * LG2 + t * LG2_L;
+    let mut z = u + v;
+    let w = v - (z - u);
+    let t = z * z;
+    let t1 = z - t * (P[0] + t * (P[1] + t * (P[2] + t * (P[3] + t * P[4]))));
+    let r = (z * t1) / (t1 - 2.0) - (w + z * w);
+
+    z = 1.0 - (r - z);
+
+    let j = high(z).wrapping_add(n << 20);
+
+    if (j >> 20) <= 0 {
+        // Subnormal output.
+        z = scalbn(z, n);
+    } else {
+        z = with_high(z, j);
+    }
+
+    s * z
+}
+
+/// Chunks of 24 bits of `2 / pi`.
+const TWO_OVER_PI: [i32; 66] = [
+    0xA2F983, 0x6E4E44, 0x1529FC, 0x2757D1, 0xF534DD, 0xC0DB62, 0x95993C, 0x439041, 0xFE5163,
+    0xABDEBB, 0xC561B7, 0x246E3A, 0x424DD2, 0xE00649, 0x2EEA09, 0xD1921C, 0xFE1DEB, 0x1CB129,
+    0xA73EE8, 0x8235F5, 0x2EBB44, 0x84E99C, 0x7026B4, 0x5F7E41, 0x3991D6, 0x398353, 0x39F49C,
+    0x845F8B, 0xBDF928, 0x3B1FF8, 0x97FFDE, 0x05980F, 0xEF2F11, 0x8B5A0A, 0x6D1F6D, 0x367ECF,
+    0x27CB09, 0xB74F46, 0x3F669E, 0x5FEA2D, 0x7527BA, 0xC7EBE5, 0xF17B3D, 0x0739F7, 0x8A5292,
+    0xEA6BFB, 0x5FB11F, 0x8D5D08, 0x560330, 0x46FC7B, 0x6BABF0, 0xCFBC20, 0x9AF436, 0x1DA9E3,
+    0x91615E, 0xE61B08, 0x659985, 0x5F14A0, 0x68408D, 0xFFD880, 0x4D7327, 0x310606, 0x1556CA,
+    0x73A8C9, 0x60E27B, 0xC08C6B,
+];
+
+/// The high words of `n * pi / 2` for `n` from 1 to 32.
+const NPIO2_HW: [i32; 32] = [
+    0x3FF921FB, 0x400921FB, 0x4012D97C, 0x401921FB, 0x401F6A7A, 0x4022D97C, 0x4025FDBB, 0x402921FB,
+    0x402C463A, 0x402F6A7A, 0x4031475C, 0x4032D97C, 0x40346B9C, 0x4035FDBB, 0x40378FDB, 0x403921FB,
+    0x403AB41B, 0x403C463A, 0x403DD85A, 0x403F6A7A, 0x40407E4C, 0x4041475C, 0x4042106C, 0x4042D97C,
+    0x4043A28C, 0x40446B9C, 0x404534AC, 0x4045FDBB, 0x4046C6CB, 0x40478FDB, 0x404858EB, 0x404921FB,
+];
+
+/// Reduce `x` by multiples of `pi / 2`, returning the quadrant and the
+/// remainder as a head and tail, a port of fdlibm's `__ieee754_rem_pio2`.
+fn rem_pio2(x: f64) -> (i32, f64, f64) {
+    const INVPIO2: f64 = ::core::f64::consts::FRAC_2_PI;
+    const PIO2_1: f64 = 1.5707963267341256;
+    const PIO2_1T: f64 = 6.077100506506192e-11;
+    const PIO2_2: f64 = 6.077100506303966e-11;
+    const PIO2_2T: f64 = 2.0222662487959506e-21;
+    const PIO2_3: f64 = 2.0222662487111665e-21;
+    const PIO2_3T: f64 = 8.4784276603689e-32;
+
+    let hx = high(x);
+    let ix = hx & 0x7fff_ffff;
+
+    if ix <= 0x3fe9_21fb {
+        // |x| <= pi / 4, no need to reduce.
+        return (0, x, 0.0);
+    }
+
+    if ix < 0x4002_d97c {
+        // |x| < 3 * pi / 4, so n is 1 or -1.
+        return if hx > 0 {
+            let z = x - PIO2_1;
+
+            if ix != 0x3ff9_21fb {
+                let y0 = z - PIO2_1T;
+
+                (1, y0, (z - y0) - PIO2_1T)
+            } else {
+                // Near pi / 2, use 33 + 33 + 53 bits of pi.
+                let z = z - PIO2_2;
+                let y0 = z - PIO2_2T;
+
+                (1, y0, (z - y0) - PIO2_2T)
+            }
+        } else {
+            let z = x + PIO2_1;
+
+            if ix != 0x3ff9_21fb {
+                let y0 = z + PIO2_1T;
+
+                (-1, y0, (z - y0) + PIO2_1T)
+            } else {
+                let z = z + PIO2_2;
+                let y0 = z + PIO2_2T;
+
+                (-1, y0, (z - y0) + PIO2_2T)
+            }
+        };
+    }
+
+    if ix <= 0x4139_21fb {
+        // |x| <= 2^19 * pi / 2, medium size.
+        let t = x.abs();
+        let n = (t * INVPIO2 + 0.5) as i32;
+        let f = n as f64;
+
+        let mut r = t - f * PIO2_1;
+        // The first round is good to 85 bits.
+        let mut w = f * PIO2_1T;
+        let mut y0 = r - w;
+
+        if n >= 32 || ix == NPIO2_HW[n as usize - 1] {
+            let j = ix >> 20;
+
+            if j - ((high(y0) >> 20) & 0x7ff) > 16 {
+                // A second iteration, good to 118 bits.
+                let t = r;
+
+                w = f * PIO2_2;
+                r = t - w;
+                w = f * PIO2_2T - ((t - r) - w);
+                y0 = r - w;
+
+                if j - ((high(y0) >> 20) & 0x7ff) > 49 {
+                    // A third, good to 151 bits, covering every case.
+                    let t = r;
+
+                    w = f * PIO2_3;
+                    r = t - w;
+                    w = f * PIO2_3T - ((t - r) - w);
+                    y0 = r - w;
+                }
+            }
+        }
+
+        let y1 = (r - y0) - w;
+
+        return if hx < 0 { (-n, -y0, -y1) } else { (n, y0, y1) };
+    }
+
+    if ix >= 0x7ff0_0000 {
+        // Infinite or NaN.
+        return (0, f64::NAN, f64::NAN);
+    }
+
+    // Split |x| * 2^-e0 into 24-bit chunks.
+    let e0 = (ix >> 20) - 1046;
+    let mut z = from_words(ix - (e0 << 20), low(x));
+    let mut tx = [0.0; 3];
+
+    for chunk in &mut tx[..2] {
+        *chunk = z as i32 as f64;
+        z = (z - *chunk) * TWO24;
+    }
+
+    tx[2] = z;
+
+    let mut nx = 3;
+
+    while tx[nx - 1] == 0.0 {
+        nx -= 1;
+    }
+
+    let (n, y0, y1) = kernel_rem_pio2(&tx[..nx], e0);
+
+    if hx < 0 {
+        (-n, -y0, -y1)
+    } else {
+        (n, y0, y1)
+    }
+}
+
+/// Reduce a large `x`, given as 24-bit chunks scaled by 2<sup>-e0</sup>,
+/// by multiples of `pi / 2`, a port of fdlibm's `__kernel_rem_pio2` for
+/// double precision.
+fn kernel_rem_pio2(x: &[f64], e0: i32) -> (i32, f64, f64) {
+    const PIO2: [f64; 8] = [
+        1.570796251296997,
+        7.549789415861596e-8,
+        5.390302529957765e-15,
+        3.282003415807913e-22,
+        1.270655753080676e-29,
+        1.2293330898111133e-36,
+        2.7337005381646456e-44,
+        2.1674168387780482e-51,
+    ];
+
+    // How many terms of the product to start with, for double precision.
+    const JK: usize = 4;
+    const JP: usize = JK;
+
+    let mut iq = [0i32; 20];
+    let mut f = [0.0f64; 20];
+    let mut fq = [0.0f64; 20];
+    let mut q = [0.0f64; 20];
+
+    let jx = x.len() - 1;
+    let jv = ((e0 - 3) / 24).max(0) as usize;
+    let mut q0 = e0 - 24 * (jv as i32 + 1);
+
+    // f[0..=jx + jk] are the chunks of 2 / pi that matter.
+    for (i, f) in f.iter_mut().enumerate().take(jx + JK + 1) {
+        let j = jv as isize - jx as isize + i as isize;
+
+        *f = if j < 0 {
+            0.0
+        } else {
+            TWO_OVER_PI[j as usize] as f64
+        };
+    }
+
+    for i in 0..=JK {
+        q[i] = (0..=jx)
+            .map(|j| x[j] * f[jx + i - j])
+            .fold(0.0, |acc, x| acc + x);
+    }
+
+    let mut jz = JK;
+
+    let (n, ih, z) = loop {
+        // Distill q into iq, reversed.
+        let mut z = q[jz];
+
+        for (i, j) in (1..=jz).rev().enumerate() {
+            let fw = (TWON24 * z) as i32 as f64;
+
+            iq[i] = (z - TWO24 * fw) as i32;
+            z = q[j - 1] + fw;
+        }
+
+        z = scalbn(z, q0);
+        // Trim off the integer part past 8.
+        z -= 8.0 * ((z * 0.125) as i64 as f64);
+
+        let mut n = z as i32;
+
+        z -= n as f64;
+
+        let mut ih = 0;
+
+        if q0 > 0 {
+            // iq[jz - 1] is needed for n.
+            let i = iq[jz - 1] >> (24 - q0);
+
+            n += i;
+            iq[jz - 1] -= i << (24 - q0);
+            ih = iq[jz - 1] >> (23 - q0);
+        } else if q0 == 0 {
+            ih = iq[jz - 1] >> 23;
+        } else if z >= 0.5 {
+            ih = 2;
+        }
+
+        if ih > 0 {
+            // q > 0.5, so take 1 - q.
+            n += 1;
+
+            let mut carry = false;
+
+            for iq in &mut iq[..jz] {
+                let j = *iq;
+
+                if !carry {
+                    if j != 0 {
+                        carry = true;
+                        *iq = 0x0100_0000 - j;
+                    }
+                } else {
+                    *iq = 0x00ff_ffff - j;
+                }
+            }
+
+            if q0 == 1 {
+                iq[jz - 1] &= 0x007f_ffff;
+            } else if q0 == 2 {
+                iq[jz - 1] &= 0x003f_ffff;
+            }
+
+            if ih == 2 {
+                z = 1.0 - z;
+
+                if carry {
+                    z -= scalbn(1.0, q0);
+                }
+            }
+        }
+
+        // Recompute with more terms if everything cancelled.
+        if z == 0.0 && iq[JK..jz].iter().all(|&iq| iq == 0) {
+            let mut k = 1;
+
+            while iq[JK - k] == 0 {
+                k += 1;
+            }
+
+            for i in jz + 1..=jz + k {
+                f[jx + i] = TWO_OVER_PI[jv + i] as f64;
+                q[i] = (0..=jx)
+                    .map(|j| x[j] * f[jx + i - j])
+                    .fold(0.0, |acc, x| acc + x);
+            }
+
+            jz += k;
+
+            continue;
+        }
+
+        break (n, ih, z);
+    };
+
+    let mut z = z;
+
+    // Chop off zero terms.
+    if z == 0.0 {
+        jz -= 1;
+        q0 -= 24;
+
+        while iq[jz] == 0 {
+            jz -= 1;
+            q0 -= 24;
+        }
+    } else {
+        // Break z into 24 bits if needed.
+        z = scalbn(z, -q0);
+
+        if z >= TWO24 {
+            let fw = (TWON24 * z) as i32 as f64;
+
+            iq[jz] = (z - TWO24 * fw) as i32;
+            jz += 1;
+            q0 += 24;
+            iq[jz] = fw as i32;
+        } else {
+            iq[jz] = z as i32;
+        }
+    }
+
+    // Convert the integer chunks to doubles.
+    let mut fw = scalbn(1.0, q0);
+
+    for i in (0..=jz).rev() {
+        q[i] = fw * iq[i] as f64;
+        fw *= TWON24;
+    }
+
+    // PIO2[0..=jp] * q[jz..=0]
+    for i in (0..=jz).rev() {
+        fq[jz - i] = (0..=JP.min(jz - i)).fold(0.0, |fw, k| fw + PIO2[k] * q[i + k]);
+    }
+
+    // Compress fq into y.
+    let fw = (0..=jz).rev().fold(0.0, |fw, i| fw + fq[i]);
+    let y0 = if ih == 0 { fw } else { -fw };
+    let fw = (1..=jz).fold(fq[0] - fw, |fw, i| fw + fq[i]);
+    let y1 = if ih == 0 { fw } else { -fw };
+
+    (n & 7, y0, y1)
+}
+
+/// fdlibm's `__kernel_sin` on `[-pi/4, pi/4]`, where `y` is the tail of
+/// `x` if `tail` is set.
+#[inline(always)]
+fn kernel_sin(x: f64, y: f64, tail: bool) -> f64 {
+    const S: [f64; 6] = [
+        -0.16666666666666632,
+        0.00833333333332249,
+        -0.0001984126982985795,
+        2.7557313707070068e-06,
+        -2.5050760253406863e-08,
+        1.58969099521155e-10,
+    ];
+
+    if (high(x) & 0x7fff_ffff) < 0x3e40_0000 && x as i32 == 0 {
+        // |x| < 2^-27
+        return x;
+    }
+
+    let z = x * x;
+    let v = z * x;
+    let r = S[1] + z * (S[2] + z * (S[3] + z * (S[4] + z * S[5])));
+
+    if !tail {
+        x + v * (S[0] + z * r)
+    } else {
+        x - ((z * (0.5 * y - v * r) - y) - v * S[0])
+    }
+}
+
+/// fdlibm's `__kernel_cos` on `[-pi/4, pi/4]`, where `y` is the tail of
+/// `x`.
+#[inline(always)]
+fn kernel_cos(x: f64, y: f64) -> f64 {
+    const C: [f64; 6] = [
+        0.0416666666666666,
+        -0.001388888888887411,
+        2.480158728947673e-05,
+        -2.7557314351390663e-07,
+        2.087572321298175e-09,
+        -1.1359647557788195e-11,
+    ];
+
+    let ix = high(x) & 0x7fff_ffff;
+
+    if ix < 0x3e40_0000 && x as i32 == 0 {
+        // |x| < 2^-27
+        return 1.0;
+    }
+
+    let z = x * x;
+    let r = z * (C[0] + z * (C[1] + z * (C[2] + z * (C[3] + z * (C[4] + z * C[5])))));
+
+    if ix < 0x3fd3_3333 {
+        // |x| < 0.3
+        return 1.0 - (0.5 * z - (z * r - x * y));
+    }
+
+    let qx = if ix > 0x3fe9_0000 {
+        0.28125
+    } else {
+        from_words(ix - 0x0020_0000, 0)
+    };
+
+    (1.0 - qx) - ((0.5 * z - qx) - (z * r - x * y))
+}
+
+/// The sine of `x`, a port of fdlibm's `sin`.
+#[inline]
+#[must_use]
+pub fn sin(x: f64) -> f64 {
+    let ix = high(x) & 0x7fff_ffff;
+
+    if ix <= 0x3fe9_21fb {
+        return kernel_sin(x, 0.0, false);
+    } else if ix >= 0x7ff0_0000 {
+        return f64::NAN;
+    }
+
+    match rem_pio2(x) {
+        (n, y0, y1) if n & 3 == 0 => kernel_sin(y0, y1, true),
+        (n, y0, y1) if n & 3 == 1 => kernel_cos(y0, y1),
+        (n, y0, y1) if n & 3 == 2 => -kernel_sin(y0, y1, true),
+        (_, y0, y1) => -kernel_cos(y0, y1),
+    }
+}
+
+/// The cosine of `x`, a port of fdlibm's `cos`.
+#[inline]
+#[must_use]
+pub fn cos(x: f64) -> f64 {
+    let ix = high(x) & 0x7fff_ffff;
+
+    if ix <= 0x3fe9_21fb {
+        return kernel_cos(x, 0.0);
+    } else if ix >= 0x7ff0_0000 {
+        return f64::NAN;
+    }
+
+    match rem_pio2(x) {
+        (n, y0, y1) if n & 3 == 0 => kernel_cos(y0, y1),
+        (n, y0, y1) if n & 3 == 1 => -kernel_sin(y0, y1, true),
+        (n, y0, y1) if n & 3 == 2 => -kernel_cos(y0, y1),
+        (_, y0, y1) => kernel_sin(y0, y1, true),
+    }
+}
@@ -0,0 +1,168 @@
+//! A lazily seeded [`JavaRng`] for each thread, with free functions drawing
+//! from it.
+
+use core::{cell::RefCell, ops::Range};
+use std::{rc::Rc, thread_local};
+
+use crate::JavaRng;
+
+thread_local! {
+    static THREAD_RNG: Rc<RefCell<JavaRng>> = Rc::new(RefCell::new(JavaRng::new_nanos()));
+}
+
+/// A handle to the random number generator of the current thread, created
+/// with [`JavaRng::new_nanos`] the first time the thread uses it.
+///
+/// Every handle of a thread shares one generator, so draws through any of
+/// them, or through the free functions, continue one sequence.
+#[derive(Debug, Clone)]
+pub struct ThreadRng {
+    rng: Rc<RefCell<JavaRng>>,
+}
+
+impl ThreadRng {
+    /// Call `f` with the generator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called again from inside `f`, through any handle or free
+    /// function.
+    #[inline]
+    pub fn with<R>(&self, f: impl FnOnce(&mut JavaRng) -> R) -> R {
+        f(&mut self.rng.borrow_mut())
+    }
+}
+
+/// Get a handle to the random number generator of the current thread.
+#[inline]
+#[must_use]
+pub fn thread_rng() -> ThreadRng {
+    ThreadRng {
+        rng: THREAD_RNG.with(Rc::clone),
+    }
+}
+
+#[inline]
+fn with<R>(f: impl FnOnce(&mut JavaRng) -> R) -> R {
+    THREAD_RNG.with(|rng| f(&mut rng.borrow_mut()))
+}
+
+/// Reset the random number generator of the current thread to the
+/// specified `seed`, like [`JavaRng::set_seed`].
+#[inline]
+pub fn seed(seed: i64) {
+    with(|rng| rng.set_seed(seed))
+}
+
+/// Like [`JavaRng::next_bool`].
+#[inline]
+#[must_use]
+pub fn bool() -> bool {
+    with(JavaRng::next_bool)
+}
+
+/// Like [`JavaRng::next_i32`].
+#[inline]
+#[must_use]
+pub fn i32() -> i32 {
+    with(JavaRng::next_i32)
+}
+
+/// Like [`JavaRng::next_i32_bounded`].
+#[inline]
+#[must_use]
+pub fn i32_bounded(bound: i32) -> i32 {
+    with(|rng| rng.next_i32_bounded(bound))
+}
+
+/// Like [`JavaRng::next_i32_ranged`], or Java's `nextInt(origin, bound)`.
+///
+/// # Panics
+///
+/// Panics if `range` is empty.
+#[inline]
+#[must_use]
+pub fn i32_range(range: Range<i32>) -> i32 {
+    with(|rng| rng.next_range(range))
+}
+
+/// Like [`JavaRng::next_i64`].
+#[inline]
+#[must_use]
+pub fn i64() -> i64 {
+    with(JavaRng::next_i64)
+}
+
+/// Like [`JavaRng::next_i64_bounded`].
+#[inline]
+#[must_use]
+pub fn i64_bounded(bound: i64) -> i64 {
+    with(|rng| rng.next_i64_bounded(bound))
+}
+
+/// Like [`JavaRng::next_i64_ranged`], or Java's `nextLong(origin, bound)`.
+///
+/// # Panics
+///
+/// Panics if `range` is empty.
+#[inline]
+#[must_use]
+pub fn i64_range(range: Range<i64>) -> i64 {
+    with(|rng| rng.next_range(range))
+}
+
+/// Like [`JavaRng::next_f32`].
+#[inline]
+#[must_use]
+pub fn f32() -> f32 {
+    with(JavaRng::next_f32)
+}
+
+/// Like [`JavaRng::next_f32_ranged`], or Java's `nextFloat(origin, bound)`.
+///
+/// # Panics
+///
+/// Panics if `range` is empty or its width is not finite.
+#[inline]
+#[must_use]
+pub fn f32_range(range: Range<f32>) -> f32 {
+    with(|rng| rng.next_range(range))
+}
+
+/// Like [`JavaRng::next_f64`].
+#[inline]
+#[must_use]
+pub fn f64() -> f64 {
+    with(JavaRng::next_f64)
+}
+
+/// Like [`JavaRng::next_f64_ranged`], or Java's `nextDouble(origin, bound)`.
+///
+/// # Panics
+///
+/// Panics if `range` is empty or its width is not finite.
+#[inline]
+#[must_use]
+pub fn f64_range(range: Range<f64>) -> f64 {
+    with(|rng| rng.next_range(range))
+}
+
+/// Like [`JavaRng::next_gaussian`].
+#[inline]
+#[must_use]
+pub fn gaussian() -> f64 {
+    with(JavaRng::next_gaussian)
+}
+
+/// Like [`JavaRng::shuffle`].
+#[inline]
+pub fn shuffle<T>(slice: &mut [T]) {
+    with(|rng| rng.shuffle(slice))
+}
+
+/// Like [`JavaRng::choose`].
+#[inline]
+#[must_use]
+pub fn choose<T>(slice: &[T]) -> Option<&T> {
+    with(|rng| rng.choose(slice))
+}
@@ -0,0 +1,115 @@
+use core::ops::Range;
+
+/// Types that can be generated uniformly in a range, each with the
+/// algorithm Java uses for it.
+///
+/// This backs the generic `next_range` of every random number generator
+/// with Java's methods, such as [`JavaRng::next_range`](crate::JavaRng).
+pub trait UniformJava: Sized {
+    /// Generate a value in `range` with `rng`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is empty, like Java's ranged methods throw, rather
+    /// than falling back to the full domain of the type. Floating-point
+    /// ranges must also have a finite width.
+    fn sample_java<R: RangedRandom + ?Sized>(rng: &mut R, range: Range<Self>) -> Self;
+}
+
+/// The ranged methods of the random number generators, which
+/// [`UniformJava`] builds on.
+#[doc(hidden)]
+pub trait RangedRandom {
+    fn next_i32_ranged(&mut self, range: Range<i32>) -> i32;
+    fn next_i64_ranged(&mut self, range: Range<i64>) -> i64;
+    fn next_f32_ranged(&mut self, range: Range<f32>) -> f32;
+    fn next_f64_ranged(&mut self, range: Range<f64>) -> f64;
+}
+
+impl UniformJava for i32 {
+    #[inline]
+    fn sample_java<R: RangedRandom + ?Sized>(rng: &mut R, range: Range<i32>) -> i32 {
+        assert!(range.start < range.end, "bound must be greater than origin");
+
+        rng.next_i32_ranged(range)
+    }
+}
+
+impl UniformJava for i64 {
+    #[inline]
+    fn sample_java<R: RangedRandom + ?Sized>(rng: &mut R, range: Range<i64>) -> i64 {
+        assert!(range.start < range.end, "bound must be greater than origin");
+
+        rng.next_i64_ranged(range)
+    }
+}
+
+impl UniformJava for f32 {
+    #[inline]
+    fn sample_java<R: RangedRandom + ?Sized>(rng: &mut R, range: Range<f32>) -> f32 {
+        assert!(
+            range.start < range.end && range.end - range.start < f32::INFINITY,
+            "bound must be greater than origin, and the range finite"
+        );
+
+        rng.next_f32_ranged(range)
+    }
+}
+
+impl UniformJava for f64 {
+    #[inline]
+    fn sample_java<R: RangedRandom + ?Sized>(rng: &mut R, range: Range<f64>) -> f64 {
+        assert!(
+            range.start < range.end && range.end - range.start < f64::INFINITY,
+            "bound must be greater than origin, and the range finite"
+        );
+
+        rng.next_f64_ranged(range)
+    }
+}
+
+/// Generated like an `int` when the range fits in one, as Java code does
+/// for indices, and like a `long` otherwise, which covers every `usize`
+/// below `2^63`.
+///
+/// # Panics
+///
+/// Panics if the range is empty or ends above `i64::MAX`.
+impl UniformJava for usize {
+    #[inline]
+    fn sample_java<R: RangedRandom + ?Sized>(rng: &mut R, range: Range<usize>) -> usize {
+        let Range { start, end } = range;
+
+        assert!(start < end, "bound must be greater than origin");
+
+        match (i32::try_from(start), i32::try_from(end)) {
+            (Ok(start), Ok(end)) => rng.next_i32_ranged(start..end) as usize,
+            _ => {
+                let end = i64::try_from(end).expect("bound is too large");
+
+                rng.next_i64_ranged(start as i64..end) as usize
+            }
+        }
+    }
+}
+
+/// Generated like `(char) nextInt(start, end)`, drawing again whenever the
+/// result is a surrogate.
+///
+/// # Panics
+///
+/// Panics if the range is empty.
+impl UniformJava for char {
+    #[inline]
+    fn sample_java<R: RangedRandom + ?Sized>(rng: &mut R, range: Range<char>) -> char {
+        assert!(range.start < range.end, "bound must be greater than origin");
+
+        let range = range.start as i32..range.end as i32;
+
+        loop {
+            if let Some(c) = char::from_u32(rng.next_i32_ranged(range.clone()) as u32) {
+                break c;
+            }
+        }
+    }
+}
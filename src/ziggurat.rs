@@ -0,0 +1,192 @@
+//! The modified ziggurat algorithms Java 17's `RandomGenerator` uses for its
+//! default `nextGaussian()` and `nextExponential()`.
+//!
+//! These consume `nextLong()` values, so any generator with a Java-exact
+//! `next_i64` produces the same gaussians and exponentials as Java through
+//! them.
+
+use crate::math;
+
+mod tables;
+use tables::*;
+
+const EXPONENTIAL_NUMBER_OF_LAYERS: u64 = 252;
+const EXPONENTIAL_X0: f64 = 7.569274694148063;
+const EXPONENTIAL_CONVEX_MARGIN: i64 = 853965788476313645;
+
+const NORMAL_NUMBER_OF_LAYERS: u64 = 253;
+const NORMAL_X0: f64 = 3.6360066255009458;
+const NORMAL_INFLECTION_INDEX: usize = 204;
+const NORMAL_CONVEX_MARGIN: i64 = 760463704284035183;
+const NORMAL_CONCAVE_MARGIN: i64 = 2269182951627976012;
+
+/// `2^63`, which scales the table entries back up for the overhangs.
+const TWO_POW_63: f64 = 9.223372036854776e18;
+
+/// Pick an overhang of the ziggurat with the alias method.
+#[inline]
+fn alias(threshold: &[i64; 256], map: &[u8; 256], ua: i64) -> usize {
+    let j = ua as u8 as usize;
+
+    if ua >= threshold[j] {
+        map[j] as usize
+    } else {
+        j
+    }
+}
+
+/// Interpolate between layers `j - 1` and `j` of a table.
+#[inline]
+fn interpolate(table: &[f64], j: usize, u: i64) -> f64 {
+    table[j] * TWO_POW_63 + (table[j - 1] - table[j]) * u as f64
+}
+
+#[inline]
+fn next_u63(next_i64: &mut impl FnMut() -> i64) -> i64 {
+    (next_i64() as u64 >> 1) as i64
+}
+
+/// Generate an exponentially distributed `f64` with mean 1, like
+/// `RandomSupport.computeNextExponential`.
+#[must_use]
+pub fn next_exponential(mut next_i64: impl FnMut() -> i64) -> f64 {
+    let mut u1 = next_i64();
+    let i = u1 as u64 & 0xff;
+
+    if i < EXPONENTIAL_NUMBER_OF_LAYERS {
+        return EXPONENTIAL_X[i as usize] * (u1 as u64 >> 1) as f64;
+    }
+
+    let mut extra = 0.0;
+
+    loop {
+        let j = alias(
+            &EXPONENTIAL_ALIAS_THRESHOLD,
+            &EXPONENTIAL_ALIAS_MAP,
+            next_i64(),
+        );
+
+        if j > 0 {
+            u1 = (u1 as u64 >> 1) as i64;
+
+            loop {
+                let mut u2 = next_u63(&mut next_i64);
+                let mut delta = u2 - u1;
+
+                if delta < 0 {
+                    delta = -delta;
+                    u2 = u1;
+                    u1 -= delta;
+                }
+
+                let x = interpolate(&EXPONENTIAL_X, j, u1);
+
+                if delta >= EXPONENTIAL_CONVEX_MARGIN {
+                    return x + extra;
+                }
+
+                let y = interpolate(&EXPONENTIAL_Y, j, u2);
+
+                if y <= math::exp(-x) {
+                    return x + extra;
+                }
+
+                u1 = next_u63(&mut next_i64);
+            }
+        }
+
+        // The tail is another exponential distribution, shifted by `X0`.
+        extra += EXPONENTIAL_X0;
+
+        u1 = next_i64();
+        let i = u1 as u64 & 0xff;
+
+        if i < EXPONENTIAL_NUMBER_OF_LAYERS {
+            return EXPONENTIAL_X[i as usize] * (u1 as u64 >> 1) as f64 + extra;
+        }
+    }
+}
+
+/// Generate a normally distributed `f64` with mean 0 and standard deviation
+/// 1, like `RandomSupport.computeNextGaussian`.
+#[must_use]
+pub fn next_gaussian(mut next_i64: impl FnMut() -> i64) -> f64 {
+    let mut u1 = next_i64();
+    let i = u1 as u64 & 0xff;
+
+    if i < NORMAL_NUMBER_OF_LAYERS {
+        return NORMAL_X[i as usize] * u1 as f64;
+    }
+
+    let sign = if u1 >= 0 { 1.0 } else { -1.0 };
+    u1 = ((u1 << 1) as u64 >> 1) as i64;
+
+    let j = alias(&NORMAL_ALIAS_THRESHOLD, &NORMAL_ALIAS_MAP, next_i64());
+    let density = |x: f64| math::exp(-0.5 * x * x);
+
+    let x = if j > NORMAL_INFLECTION_INDEX {
+        loop {
+            let u2 = next_u63(&mut next_i64);
+            let x = interpolate(&NORMAL_X, j, u1);
+            let delta = u2 - u1;
+
+            if delta >= 0 {
+                break x;
+            }
+
+            if delta > -NORMAL_CONCAVE_MARGIN && interpolate(&NORMAL_Y, j, u2) <= density(x) {
+                break x;
+            }
+
+            u1 = next_u63(&mut next_i64);
+        }
+    } else if j == 0 {
+        // The tail, sampled with Marsaglia's method.
+        let x = loop {
+            let x = (1.0 / NORMAL_X0) * next_exponential(&mut next_i64);
+
+            if next_exponential(&mut next_i64) >= 0.5 * x * x {
+                break x;
+            }
+        };
+
+        x + NORMAL_X0
+    } else if j < NORMAL_INFLECTION_INDEX {
+        loop {
+            let mut u2 = next_u63(&mut next_i64);
+            let mut delta = u2 - u1;
+
+            if delta < 0 {
+                delta = -delta;
+                u2 = u1;
+                u1 -= delta;
+            }
+
+            let x = interpolate(&NORMAL_X, j, u1);
+
+            if delta >= NORMAL_CONVEX_MARGIN || interpolate(&NORMAL_Y, j, u2) <= density(x) {
+                break x;
+            }
+
+            u1 = next_u63(&mut next_i64);
+        }
+    } else {
+        loop {
+            let u2 = next_u63(&mut next_i64);
+            let x = interpolate(&NORMAL_X, j, u1);
+            let delta = u2 - u1;
+
+            if delta >= NORMAL_CONVEX_MARGIN {
+                break x;
+            }
+
+            if delta > -NORMAL_CONCAVE_MARGIN && interpolate(&NORMAL_Y, j, u2) <= density(x) {
+                break x;
+            }
+
+            u1 = next_u63(&mut next_i64);
+        }
+    };
+
+    sign * x
+}